@@ -5,7 +5,6 @@ use num_traits::identities::One;    // Needed for one constructions
 use ark_ff::fields::Field;          // Needed for pow
 use ark_ff::ToBytes;
 use ark_std::rand::Rng as ArkRng;   // Must be in scope for rscalar
-use ark_bls12_381;
 
 use sha2::Digest;
 use std::convert::TryInto;
@@ -21,8 +20,8 @@ use criterion::{
 };
 
 use backend::{scalar, genG1, genG2, zeroG1, zeroG2,
-    pow, contained_in_group, add1, add2, smul1, smul2, pair,
-    bytes1, bytes2, hashG1,
+    pow, contained_in_group, add1, add2, smul1, smul2, msm1, pair,
+    bytes1, bytes2, hashG1, hash_to_curve,
 };
 
 
@@ -155,13 +154,30 @@ fn bench_bytes2(c: &mut Criterion) {
     );
 }
 
+fn bench_msm1(c: &mut Criterion) {
+    let mut group = c.benchmark_group("msm1");
+    for size in [10, 100, 1000].iter() {
+        let G = genG1!();
+        let bases = (1..*size + 1)
+            .map(|i| smul1!(scalar!(i as u64), G))
+            .collect::<Vec<_>>();
+        let scalars = (0..*size)
+            .map(|i| scalar!(1000 + i as u64))
+            .collect::<Vec<_>>();
+        group.bench_function(
+            format!("Compute MSM of size {}", size),
+            |b| b.iter(|| msm1!(bases, scalars)),
+        );
+    }
+    group.finish();
+}
+
 fn bench_hashG1(c: &mut Criterion) {
-    use sha2::Digest;
-    use std::convert::TryInto;
     let bytes: Vec<u8> = (0..5).collect();
+    let dst = hash_to_curve::dst_g1("SNARKY-V01-CS01-BENCH");
     c.bench_function(
         "hashG1!",
-        |b| b.iter(|| hashG1!(&bytes))
+        |b| b.iter(|| hashG1!(&bytes, &dst))
     );
 }
 
@@ -173,6 +189,7 @@ criterion_group!(
     bench_add2,
     bench_smul1,
     bench_smul2,
+    bench_msm1,
     bench_pair,
     bench_bytes1,
     bench_bytes2,
@@ -38,7 +38,7 @@ fn main() {
 
     // Raising scalars to power
 
-    let base = ark_bls12_381::Fr::from(666_u64);
+    let base = Scalar::from(666_u64);
     let exp  = 999;
     let start = Instant::now();
     pow!(base, exp);
@@ -48,7 +48,7 @@ fn main() {
 
     let beast = scalar!(666_u64);
     let start = Instant::now();
-    inv!(beast);
+    inv!(beast).unwrap();
     println!("[+] inv ({:.2?})", start.elapsed());
 
     // Generators
@@ -135,14 +135,10 @@ fn main() {
 
     // G1-hash
 
-    use std::convert::TryInto;
-    use std::io::Cursor;
-    use sha2::Digest;
-    use ark_ff::FromBytes;
-
     let bytes: Vec<u8> = (0..5).collect();
+    let dst = hash_to_curve::dst_g1("SNARKY-V01-CS01-EXAMPLE");
 
     let start = Instant::now();
-    hashG1!(&bytes);
+    let _ = hashG1!(&bytes, &dst);
     println!("[+] Computed G1-hhash ({:.2?})", start.elapsed());
 }
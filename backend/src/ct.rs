@@ -0,0 +1,62 @@
+// Constant-time equality of scalars, group elements and pairing values,
+// based on their canonical byte encodings. Mirrors subtle::ConstantTimeEq,
+// which cannot be implemented here for the (foreign) arkworks types.
+
+use crate::{Scalar, GtElem};
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+use ark_ec::SWModelParameters;
+use ark_ff::{BigInteger, PrimeField};
+use ark_serialize::{CanonicalSerialize, SerializationError};
+use subtle::Choice;
+
+pub trait ConstantTimeEq {
+
+    fn ct_eq(&self, other: &Self) -> Choice;
+
+    fn ct_ne(&self, other: &Self) -> Choice {
+        !self.ct_eq(other)
+    }
+}
+
+// Inputs of equal length are compared in constant time
+fn ct_eq_bytes(left: &[u8], right: &[u8]) -> Choice {
+    subtle::ConstantTimeEq::ct_eq(left, right)
+}
+
+// Elements failing to serialize (never the case for in-memory buffers) are
+// considered unequal
+fn ct_eq_encodings(left: Result<Vec<u8>, SerializationError>, right: Result<Vec<u8>, SerializationError>)
+    -> Choice
+{
+    match (left, right) {
+        (Ok(left), Ok(right))   => ct_eq_bytes(&left, &right),
+        _                       => Choice::from(0),
+    }
+}
+
+impl ConstantTimeEq for Scalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        ct_eq_bytes(&self.into_repr().to_bytes_le(), &other.into_repr().to_bytes_le())
+    }
+}
+
+// Covers both G1Elem and G2Elem, via the uncompressed encoding
+impl<P: SWModelParameters> ConstantTimeEq for GroupAffine<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let encode = |elem: &Self| {
+            let mut buffer = Vec::with_capacity(elem.uncompressed_size());
+            elem.serialize_uncompressed(&mut buffer).map(|_| buffer)
+        };
+        ct_eq_encodings(encode(self), encode(other))
+    }
+}
+
+impl ConstantTimeEq for GtElem {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let encode = |elem: &Self| {
+            let mut buffer = Vec::with_capacity(elem.serialized_size());
+            elem.serialize(&mut buffer).map(|_| buffer)
+        };
+        ct_eq_encodings(encode(self), encode(other))
+    }
+}
@@ -0,0 +1,78 @@
+// Fixed-base scalar multiplication by means of precomputed window tables.
+// For a base P and window size c, the table holds d 2^(kc) P for all digits
+// 0 <= d < 2^c and windows k, so that s P is obtained with one mixed
+// addition per window and no doublings. Building the table costs about
+// 2^c (b / c) additions for b-bit scalars, which pays off when the same
+// base is multiplied by many scalars (e.g., during SRS generation).
+
+use crate::msm::digit;
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{PrimeField, Zero};
+use rayon::prelude::*;
+
+// Window size for the provided number of scalars
+fn window_size(nr_scalars: usize) -> usize {
+    if nr_scalars < 32 {
+        3
+    } else {
+        (ark_std::log2(nr_scalars) as usize * 69 / 100).clamp(3, 16)
+    }
+}
+
+pub struct FixedBase<G: AffineCurve> {
+    window: usize,
+    table: Vec<Vec<G>>,
+}
+
+impl<G: AffineCurve> FixedBase<G> {
+
+    // Table suited for multiplying the provided base by about nr_scalars scalars
+    pub fn new(base: &G, nr_scalars: usize) -> Self {
+        let window = window_size(nr_scalars);
+        let nr_bits = <G::ScalarField as PrimeField>::size_in_bits();
+        let nr_windows = nr_bits.div_ceil(window);
+
+        let mut start = base.into_projective();
+        let table = (0..nr_windows)
+            .map(|_| {
+                let mut row = Vec::with_capacity(1 << window);
+                let mut acc = G::Projective::zero();
+                for _ in 0..1 << window {
+                    row.push(acc);
+                    acc += &start;
+                }
+                start = acc;                    // 2^c times the previous start
+                G::Projective::batch_normalization_into_affine(&row)
+            })
+            .collect();
+        Self { window, table }
+    }
+
+    pub fn mul(&self, scalar: &G::ScalarField) -> G::Projective {
+        let repr = scalar.into_repr();
+        let mut acc = G::Projective::zero();
+        for (k, row) in self.table.iter().enumerate() {
+            acc.add_assign_mixed(&row[digit(&repr, k * self.window, self.window)]);
+        }
+        acc
+    }
+
+    pub fn batch_mul(&self, scalars: &[G::ScalarField]) -> Vec<G> {
+        let out = scalars
+            .par_iter()
+            .map(|scalar| self.mul(scalar))
+            .collect::<Vec<_>>();
+        G::Projective::batch_normalization_into_affine(&out)
+    }
+}
+
+// Powers 1, x, ..., x^(count - 1), computed incrementally
+pub fn powers<F: PrimeField>(x: F, count: usize) -> Vec<F> {
+    let mut out = Vec::with_capacity(count);
+    let mut acc = F::one();
+    for _ in 0..count {
+        out.push(acc);
+        acc *= x;
+    }
+    out
+}
@@ -0,0 +1,111 @@
+// Hashing to G1 as specified by RFC 9380 (hash-to-curve), with the random
+// oracle suite of the curve in use:
+//
+// BLS12381G1_XMD:SHA-256_SSWU_RO_, i.e., expand_message_xmd with SHA-256,
+// simplified SWU map to the 11-isogenous curve and cofactor clearing. The
+// map is computed by the zkcrypto implementation and the result is
+// transferred via its zcash encoding.
+//
+// BN254G1_XMD:SHA-256_SVDW_RO_, i.e., expand_message_xmd with SHA-256 and
+// Shallue-van de Woestijne map (see the svdw module), as used by gnark.
+//
+// In both cases, the discrete logarithm of the output is unknown with
+// respect to any point.
+
+use crate::G1Elem;
+use util::HashError;
+use sha2::{Digest, Sha256};
+
+#[cfg(not(feature = "bn254"))]
+pub const SUITE_G1: &[u8] = b"BLS12381G1_XMD:SHA-256_SSWU_RO_";
+#[cfg(feature = "bn254")]
+pub const SUITE_G1: &[u8] = b"BN254G1_XMD:SHA-256_SVDW_RO_";
+
+
+// Domain separation tag of the form <application>-with-<suite>
+pub fn dst_g1(application: &str) -> Vec<u8> {
+    [application.as_bytes(), b"-with-", SUITE_G1].concat()
+}
+
+// RFC 9380, Section 5.3.1, with SHA-256. Fails if the requested length
+// exceeds 255 blocks or the dst exceeds 255 bytes.
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>, HashError> {
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+
+    let ell = len.div_ceil(B_IN_BYTES);
+    if ell > 255 || len > 0xffff {
+        return Err(HashError::LengthTooLarge);
+    }
+    if dst.len() > 255 {
+        return Err(HashError::DstTooLong);
+    }
+    let dst_prime = [dst, &[dst.len() as u8]].concat();
+
+    let b_0 = Sha256::new()
+        .chain([0u8; S_IN_BYTES])
+        .chain(msg)
+        .chain((len as u16).to_be_bytes())
+        .chain([0u8])
+        .chain(&dst_prime)
+        .finalize();
+    let mut b_i = Sha256::new()
+        .chain(b_0)
+        .chain([1u8])
+        .chain(&dst_prime)
+        .finalize();
+    let mut out = Vec::with_capacity(ell * B_IN_BYTES);
+    out.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let xored = b_0
+            .iter()
+            .zip(b_i.iter())
+            .map(|(l, r)| l ^ r)
+            .collect::<Vec<u8>>();
+        b_i = Sha256::new()
+            .chain(xored)
+            .chain([i as u8])
+            .chain(&dst_prime)
+            .finalize();
+        out.extend_from_slice(&b_i);
+    }
+    out.truncate(len);
+    Ok(out)
+}
+
+// Fails if dst is empty, as forbidden by the RFC, or exceeds 255 bytes
+#[cfg(not(feature = "bn254"))]
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> Result<G1Elem, HashError> {
+    use crate::zcash::g1_from_uncompressed;
+    use bls12_381::G1Projective;
+    use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+
+    if dst.is_empty() {
+        return Err(HashError::EmptyDst);
+    }
+    if dst.len() > 255 {
+        return Err(HashError::DstTooLong);
+    }
+    let elem = <G1Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(msg, dst);
+    let bytes = bls12_381::G1Affine::from(elem).to_uncompressed();
+    Ok(g1_from_uncompressed(&bytes).unwrap())   // Always on curve
+}
+
+// Fails if dst is empty, as forbidden by the RFC, or exceeds 255 bytes
+#[cfg(feature = "bn254")]
+pub fn hash_to_g1(msg: &[u8], dst: &[u8]) -> Result<G1Elem, HashError> {
+    use crate::curve::Fq;
+    use crate::svdw::map_to_curve;
+    use ark_ec::AffineCurve;
+    use ark_ff::PrimeField;
+
+    const L: usize = 48;    // ceil((ceil(log2(p)) + k) / 8) with k = 128
+
+    if dst.is_empty() {
+        return Err(HashError::EmptyDst);
+    }
+    let bytes = expand_message_xmd(msg, dst, 2 * L)?;
+    let u0 = Fq::from_be_bytes_mod_order(&bytes[..L]);
+    let u1 = Fq::from_be_bytes_mod_order(&bytes[L..]);
+    Ok((map_to_curve(&u0).into_projective() + map_to_curve(&u1).into_projective()).into())
+}
@@ -1,26 +1,90 @@
-pub type Scalar = ::ark_bls12_381::Fr;
-pub type G1Elem = ::ark_bls12_381::G1Affine;
-pub type G2Elem = ::ark_bls12_381::G2Affine;
+// The pairing-friendly curve is selected at compile time: BLS12-381 by
+// default, BN254 (alt_bn128, as precompiled by Ethereum) with the bn254
+// feature. Everything below is expressed in terms of the following aliases.
+
+#[cfg(not(feature = "bn254"))]
+pub mod curve {
+    pub use ark_bls12_381::{g1, g2, Fq, Fq2, Fr, G1Affine, G2Affine};
+    pub use ark_bls12_381::Bls12_381 as Engine;
+    pub use ark_bls12_381::Fq12 as GtElem;
+}
+
+#[cfg(feature = "bn254")]
+pub mod curve {
+    pub use ark_bn254::{g1, g2, Fq, Fq2, Fr, G1Affine, G2Affine};
+    pub use ark_bn254::Bn254 as Engine;
+    pub use ark_bn254::Fq12 as GtElem;
+}
+
+pub use curve::{Engine, GtElem};
+
+pub type Scalar = curve::Fr;
+pub type G1Elem = curve::G1Affine;
+pub type G2Elem = curve::G2Affine;
+
+pub use ark_serialize;
+
+// Whether the curve is BN254. Dependent crates should rely on this (and on the
+// cfg_bls12_381, cfg_bn254 macros) rather than on a bn254 feature of their own,
+// which is not set when the backend feature is enabled directly
+pub const BN254: bool = cfg!(feature = "bn254");
+
+// Items compiled only over BLS12-381
+#[cfg(not(feature = "bn254"))]
+#[macro_export]
+macro_rules! cfg_bls12_381 {
+    ($($item:item)*) => { $($item)* }
+}
+
+#[cfg(feature = "bn254")]
+#[macro_export]
+macro_rules! cfg_bls12_381 {
+    ($($item:item)*) => {}
+}
+
+// Items compiled only over BN254
+#[cfg(not(feature = "bn254"))]
+#[macro_export]
+macro_rules! cfg_bn254 {
+    ($($item:item)*) => {}
+}
+
+#[cfg(feature = "bn254")]
+#[macro_export]
+macro_rules! cfg_bn254 {
+    ($($item:item)*) => { $($item)* }
+}
+
+#[cfg(not(feature = "bn254"))]
+pub mod zcash;
+#[cfg(feature = "bn254")]
+pub mod svdw;
+pub mod hash_to_curve;
+pub mod subgroup;
+pub mod ct;
+pub mod msm;
+pub mod multi_pairing;
+pub mod fixed_base;
 
 
 #[macro_export]
 macro_rules! scalar {
     ($num:expr) => {
-        ::ark_bls12_381::Fr::from($num)
+        $crate::Scalar::from($num)
     }
 }
 
 #[macro_export]
 macro_rules! zero {
     () => {
-        ::ark_bls12_381::Fr::zero()
+        $crate::Scalar::zero()
     }
 }
 
 #[macro_export]
 macro_rules! one {
     () => {
-        ::ark_bls12_381::Fr::one()
+        $crate::Scalar::one()
     }
 }
 
@@ -28,7 +92,7 @@ macro_rules! one {
 macro_rules! rscalar {
     ($rng:expr) => {
         {
-            let rnd: ark_bls12_381::Fr = $rng.gen(); 
+            let rnd: $crate::Scalar = $rng.gen(); 
             rnd
         }
     }
@@ -37,42 +101,46 @@ macro_rules! rscalar {
 #[macro_export]
 macro_rules! genG1 {
     () => {
-        ark_bls12_381::G1Affine::prime_subgroup_generator()
+        $crate::G1Elem::prime_subgroup_generator()
     }
 }
 
 #[macro_export]
 macro_rules! genG2 {
     () => {
-        ark_bls12_381::G2Affine::prime_subgroup_generator()
+        $crate::G2Elem::prime_subgroup_generator()
     }
 }
 
 #[macro_export]
 macro_rules! zeroG1 {
     () => {
-        ::ark_bls12_381::G1Affine::zero()
+        $crate::G1Elem::zero()
     }
 }
 
 #[macro_export]
 macro_rules! zeroG2 {
     () => {
-        ::ark_bls12_381::G2Affine::zero()
+        $crate::G2Elem::zero()
     }
 }
 
 #[macro_export]
 macro_rules! unit {
     () => {
-        ::ark_bls12_381::Fq12::one()
+        $crate::GtElem::one()
     }
 }
 
 #[macro_export]
 macro_rules! contained_in_group {
+    // Curve equation and membership in the prime order subgroup
     ($elem:expr) => {
-        bool::from($elem.is_on_curve())
+        {
+            use $crate::subgroup::SubgroupCheck;
+            $elem.in_prime_subgroup()
+        }
     }
 }
 
@@ -99,33 +167,108 @@ macro_rules! bytes2 {
 }
 
 #[macro_export]
-// WARNING!: This is for the moment common equality check. It is
-// constant time only provided that equality provided by the wrapped
-// library is really constant time.
+macro_rules! write1 {
+    // compressed must be of type bool
+    ($writer:expr, $elem:expr, $compressed:expr) => {
+        match $compressed {
+            true  => $crate::ark_serialize::CanonicalSerialize::serialize(
+                &$elem, $writer
+            ),
+            false => $crate::ark_serialize::CanonicalSerialize::serialize_uncompressed(
+                &$elem, $writer
+            ),
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! write2 {
+    // compressed must be of type bool
+    ($writer:expr, $elem:expr, $compressed:expr) => {
+        match $compressed {
+            true  => $crate::ark_serialize::CanonicalSerialize::serialize(
+                &$elem, $writer
+            ),
+            false => $crate::ark_serialize::CanonicalSerialize::serialize_uncompressed(
+                &$elem, $writer
+            ),
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! read1 {
+    // compressed must be of type bool; fails for points off the curve
+    ($reader:expr, $compressed:expr) => {
+        {
+            let res: Result<$crate::G1Elem, _> = match $compressed {
+                true  => $crate::ark_serialize::CanonicalDeserialize::deserialize(
+                    $reader
+                ),
+                false => $crate::ark_serialize::CanonicalDeserialize::deserialize_uncompressed(
+                    $reader
+                ),
+            };
+            match res {
+                Ok(elem) if !elem.is_on_curve() => Err(
+                    $crate::ark_serialize::SerializationError::InvalidData
+                ),
+                _ => res,
+            }
+        }
+    }
+}
+
+#[macro_export]
+macro_rules! read2 {
+    // compressed must be of type bool; fails for points off the curve
+    ($reader:expr, $compressed:expr) => {
+        {
+            let res: Result<$crate::G2Elem, _> = match $compressed {
+                true  => $crate::ark_serialize::CanonicalDeserialize::deserialize(
+                    $reader
+                ),
+                false => $crate::ark_serialize::CanonicalDeserialize::deserialize_uncompressed(
+                    $reader
+                ),
+            };
+            match res {
+                Ok(elem) if !elem.is_on_curve() => Err(
+                    $crate::ark_serialize::SerializationError::InvalidData
+                ),
+                _ => res,
+            }
+        }
+    }
+}
+
+#[macro_export]
+// Constant-time equality of scalars, group elements and pairing values
 macro_rules! ct_eq {
     ($elem1:expr, $elem2:expr) => {
-        // TODO: Properly implement when constant-time interface 
-        // is ready for ark_bls12_381?
-        $elem1 == $elem2
+        {
+            use $crate::ct::ConstantTimeEq;
+            bool::from(($elem1).ct_eq(&($elem2)))
+        }
     }
 }
 
 #[macro_export]
-// WARNING!: This is for the moment common inequality check. It is
-// constant time only provided that equality provided by the wrapped
-// library is really constant time.
+// Constant-time inequality of scalars, group elements and pairing values
 macro_rules! ct_ne {
     ($elem1:expr, $elem2:expr) => {
-        // TODO: Properly implement when constant-time interface 
-        // is ready for ark_bls12_381?
-        $elem1 != $elem2
+        {
+            use $crate::ct::ConstantTimeEq;
+            bool::from(($elem1).ct_ne(&($elem2)))
+        }
     }
 }
 
 #[macro_export]
+// None if the element is zero
 macro_rules! inv {
     ($elem:expr) => {
-       $elem.inverse().unwrap() 
+       $elem.inverse()
     }
 }
 
@@ -140,7 +283,7 @@ macro_rules! pow {
 macro_rules! add1 {
     ($($elem:expr), *) => {
         {
-            let mut sum = ::ark_bls12_381::G1Affine::zero();
+            let mut sum = $crate::G1Elem::zero();
             $(sum += &$elem;)*
             sum
         }
@@ -151,7 +294,7 @@ macro_rules! add1 {
 macro_rules! add2 {
     ($($elem:expr), *) => {
         {
-            let mut sum = ::ark_bls12_381::G2Affine::zero();
+            let mut sum = $crate::G2Elem::zero();
             $(sum += &$elem;)*
             sum
         }
@@ -161,58 +304,51 @@ macro_rules! add2 {
 #[macro_export]
 macro_rules! smul1 {
     ($factor: expr, $elem: expr) => {
-        ::ark_bls12_381::G1Affine::from($elem.mul($factor));
+        $crate::G1Elem::from($elem.mul($factor));
     }
 }
 
 #[macro_export]
 macro_rules! smul2 {
     ($factor: expr, $elem: expr) => {
-        ::ark_bls12_381::G2Affine::from($elem.mul($factor));
+        $crate::G2Elem::from($elem.mul($factor));
+    }
+}
+
+#[macro_export]
+macro_rules! msm1 {
+    ($bases: expr, $scalars: expr) => {
+        $crate::msm::msm1(&$bases, &$scalars)
+    }
+}
+
+#[macro_export]
+macro_rules! msm2 {
+    ($bases: expr, $scalars: expr) => {
+        $crate::msm::msm2(&$bases, &$scalars)
     }
 }
 
 #[macro_export]
 macro_rules! pair {
     ($left:expr, $right:expr) => {
-        ::ark_bls12_381::Bls12_381::pairing($left, $right)
+        $crate::Engine::pairing($left, $right)
+    }
+}
+
+#[macro_export]
+// Checks that the product of the provided pairings is the unit
+macro_rules! multi_pairing_eq {
+    ($(($left:expr, $right:expr)),+ $(,)?) => {
+        $crate::multi_pairing::multi_pairing_eq(&[$(($left, $right)),+])
     }
 }
 
 
 #[macro_export]
 macro_rules! hashG1 {
-    // bytes must be of type &[u8]
-    ($bytes:expr) => {
-        {
-            // Alternative Sha256 version
-            //
-            // let mut hasher = ::sha2::Sha256::default();
-            // hasher.update($bytes);
-            // let buffer: [u8; 32] = hasher.finalize().try_into().unwrap();
-            
-            let mut hasher = ::sha2::Sha512::default();
-            hasher.update($bytes);
-            let buffer: [u8; 64] = hasher.finalize().as_slice().try_into().unwrap();
-
-            use ark_ff::BigInteger;
-            use ark_ff::FpParameters;
-            use ark_ff::PrimeField;
-            let MODULUS = ark_bls12_381::FrParameters::MODULUS;
-            let mut b = ::ark_ff
-                ::biginteger
-                ::BigInteger256
-                ::read(Cursor::new(buffer))
-                .unwrap();
-            while (b >= MODULUS) {
-                b.sub_noborrow(&MODULUS);
-            }
-
-            // Panics if b is not normalized as above!
-            let factor = ::ark_bls12_381::Fr::from_repr(b).unwrap();
-            ::ark_bls12_381::G1Affine::from(
-                ::ark_bls12_381::G1Affine::prime_subgroup_generator().mul(factor)
-            )
-        }
+    // bytes and dst must be of type &[u8]; fails upon invalid dst
+    ($bytes:expr, $dst:expr) => {
+        $crate::hash_to_curve::hash_to_g1($bytes, $dst)
     }
 }
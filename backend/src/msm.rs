@@ -0,0 +1,93 @@
+// Variable-base multi-scalar multiplication sum_i s_i P_i by means of the
+// bucket method (Pippenger). Scalars are split into windows of c bits; for
+// each window, bases are accumulated into 2^c - 1 buckets according to the
+// respective digit and the buckets are combined by running sums, so that
+// the cost is roughly (b / c) (N + 2^c) additions for b-bit scalars, as
+// opposed to about b N / 2 additions and b doublings per term when
+// multiplying separately. Windows are processed in parallel.
+
+use crate::{G1Elem, G2Elem, Scalar};
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use rayon::prelude::*;
+
+// Window size for the provided number of terms
+fn window_size(len: usize) -> usize {
+    if len < 32 {
+        3
+    } else {
+        (ark_std::log2(len) as usize * 69 / 100) + 2
+    }
+}
+
+// Unsigned digit of the provided bit width at the provided bit offset
+pub(crate) fn digit<B: BigInteger>(repr: &B, offset: usize, width: usize) -> usize {
+    let limbs = repr.as_ref();
+    let (index, shift) = (offset / 64, offset % 64);
+    if index >= limbs.len() {
+        return 0;
+    }
+    let mut out = limbs[index] >> shift;
+    if shift + width > 64 && index + 1 < limbs.len() {
+        out |= limbs[index + 1] << (64 - shift);
+    }
+    (out & ((1u64 << width) - 1)) as usize
+}
+
+// Terms beyond the shorter of bases and scalars are ignored
+pub fn msm<G: AffineCurve>(bases: &[G], scalars: &[G::ScalarField]) -> G::Projective {
+    let len = bases.len().min(scalars.len());
+    let reprs = scalars[..len]
+        .par_iter()
+        .map(|s| s.into_repr())
+        .collect::<Vec<_>>();
+    let bases = &bases[..len];
+
+    let c = window_size(len);
+    let nr_bits = <G::ScalarField as PrimeField>::size_in_bits();
+    let windows = (0..nr_bits)
+        .step_by(c)
+        .collect::<Vec<usize>>()
+        .into_par_iter()
+        .map(|offset| {
+            let mut buckets = vec![G::Projective::zero(); (1 << c) - 1];
+            for (base, repr) in bases.iter().zip(reprs.iter()) {
+                let d = digit(repr, offset, c);
+                if d != 0 && !base.is_zero() {
+                    buckets[d - 1].add_assign_mixed(base);
+                }
+            }
+
+            // sum_d d B_d = sum_d (B_d + ... + B_{2^c - 1})
+            let mut running = G::Projective::zero();
+            let mut sum = G::Projective::zero();
+            for bucket in buckets.into_iter().rev() {
+                running += &bucket;
+                sum += &running;
+            }
+            sum
+        })
+        .collect::<Vec<_>>();
+
+    // Horner over the windows, starting from the most significant one
+    windows
+        .into_iter()
+        .rev()
+        .fold(G::Projective::zero(), |acc, window| {
+            let mut acc = acc;
+            if !acc.is_zero() {
+                for _ in 0..c {
+                    acc.double_in_place();
+                }
+            }
+            acc + window
+        })
+}
+
+pub fn msm1(bases: &[G1Elem], scalars: &[Scalar]) -> G1Elem {
+    msm(bases, scalars).into_affine()
+}
+
+pub fn msm2(bases: &[G2Elem], scalars: &[Scalar]) -> G2Elem {
+    msm(bases, scalars).into_affine()
+}
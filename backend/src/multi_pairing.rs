@@ -0,0 +1,62 @@
+// Product-of-pairings checks. An equation e(A, B) = e(C, D) is equivalent to
+// e(A, B) e(-C, D) = 1, which can be decided with a single (multi-)Miller
+// loop and a single final exponentiation, instead of one final
+// exponentiation per pairing. For large products the Miller loop is split
+// into chunks processed in parallel.
+
+use crate::{Engine, G1Elem, G2Elem, GtElem, Scalar};
+use crate::ct::ConstantTimeEq;
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use ark_ff::One;
+use rayon::prelude::*;
+
+const CHUNK_SIZE: usize = 16;
+
+// Pairs (P_i, Q_i) standing for the equation prod_i e(P_i, Q_i) = 1
+pub type Equation = Vec<(G1Elem, G2Elem)>;
+
+// Product of e(P_i, Q_i) over the provided pairs; None only if the final
+// exponentiation fails, which never happens for points on the curves
+pub fn multi_pairing(pairs: &[(G1Elem, G2Elem)]) -> Option<GtElem> {
+    let miller = pairs
+        .par_chunks(CHUNK_SIZE)
+        .map(|chunk| {
+            let prepared = chunk
+                .iter()
+                .map(|(p, q)| ((*p).into(), (*q).into()))
+                .collect::<Vec<_>>();
+            Engine::miller_loop(prepared.iter())
+        })
+        .reduce(GtElem::one, |acc, inc| acc * inc);
+    Engine::final_exponentiation(&miller)
+}
+
+// Checks in constant time that the product of e(P_i, Q_i) is the unit
+pub fn multi_pairing_eq(pairs: &[(G1Elem, G2Elem)]) -> bool {
+    match multi_pairing(pairs) {
+        Some(prod)  => bool::from(prod.ct_eq(&GtElem::one())),
+        None        => false,
+    }
+}
+
+// Checks all provided equations at once, by raising the k-th product to the
+// k-th of the provided scalars and multiplying the results. If the scalars
+// are random and some equation fails, the check fails with overwhelming
+// probability; it is unsound for scalars known in advance to the prover.
+// Fails if the numbers of equations and scalars differ.
+pub fn multi_pairing_eq_batched(equations: &[Equation], scalars: &[Scalar]) -> bool {
+    if equations.len() != scalars.len() {
+        return false;
+    }
+    let pairs = equations
+        .par_iter()
+        .zip(scalars.par_iter())
+        .flat_map(|(pairs, &r)| {
+            pairs
+                .iter()
+                .map(|(p, q)| (p.mul(r).into_affine(), *q))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    multi_pairing_eq(&pairs)
+}
@@ -0,0 +1,159 @@
+// Membership tests for the prime order subgroups G1, G2.
+//
+// BLS12-381: tests based on efficiently computable endomorphisms (Scott, "A note on group
+// membership tests for G1, G2 and GT on BLS pairing-friendly curves",
+// ePrint 2021/1130). With z = -0xd201000000010000 the curve parameter:
+//
+// G1: P in G1 iff sigma(P) = -z^2 P, where sigma(x, y) = (beta x, y)
+//     for an appropriate cube root of unity beta
+// G2: P in G2 iff psi(P) = z P, where psi is the untwist-Frobenius-twist
+//     endomorphism (x, y) -> (cx * conj(x), cy * conj(y))
+//
+// Both are far cheaper than multiplication by the group order. The
+// endomorphism constants are derived once and validated against the
+// generators.
+//
+// BN254: G1 is the whole curve (cofactor 1), so that the curve equation
+// suffices; G2 is tested by multiplication by the group order.
+
+use ark_ec::short_weierstrass_jacobian::GroupAffine;
+
+#[cfg(not(feature = "bn254"))]
+mod bls {
+    use crate::{G1Elem, G2Elem};
+    use ark_bls12_381::{Fq, Fq2, FqParameters};
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::{BigInteger, BigInteger256, BigInteger384, Field, FpParameters, One, Zero};
+    use std::sync::OnceLock;
+
+    const Z: u64 = 0xd201000000010000;          // Absolute value; z is negative
+
+
+    struct Constants {
+        beta: Fq,
+        cx: Fq2,
+        cy: Fq2,
+    }
+
+    // (p - 1) / d for small d dividing p - 1
+    fn modulus_minus_one_div(d: u64) -> BigInteger384 {
+        let mut limbs = FqParameters::MODULUS;
+        limbs.sub_noborrow(&BigInteger384::from(1));
+        let mut rem = 0u128;
+        for limb in limbs.0.iter_mut().rev() {
+            let cur = (rem << 64) | *limb as u128;
+            *limb = (cur / d as u128) as u64;
+            rem = cur % d as u128;
+        }
+        limbs
+    }
+
+    fn sigma(elem: &G1Elem, beta: &Fq) -> G1Elem {
+        G1Elem::new(elem.x * beta, elem.y, elem.infinity)
+    }
+
+    fn psi(elem: &G2Elem, cx: &Fq2, cy: &Fq2) -> G2Elem {
+        let mut x = elem.x;
+        let mut y = elem.y;
+        x.frobenius_map(1);
+        y.frobenius_map(1);
+        G2Elem::new(x * cx, y * cy, elem.infinity)
+    }
+
+    fn constants() -> &'static Constants {
+        static CONSTANTS: OnceLock<Constants> = OnceLock::new();
+        CONSTANTS.get_or_init(|| {
+
+            // Primitive cube root of unity, chosen so that sigma acts on G1
+            // as multiplication by -z^2
+            let exp = modulus_minus_one_div(3);
+            let root = (2u64..)
+                .map(|w| Fq::from(w).pow(exp))
+                .find(|root| !root.is_one())
+                .unwrap();
+            let gen = G1Elem::prime_subgroup_generator();
+            let expected = g1_mul_minus_z_squared(&gen);
+            let beta = [root, root.square()]
+                .iter()
+                .copied()
+                .find(|beta| sigma(&gen, beta).into_projective() == expected)
+                .unwrap();
+
+            // cx = (u + 1)^(-(p - 1)/3), cy = (u + 1)^(-(p - 1)/2)
+            let xi = Fq2::new(Fq::one(), Fq::one());
+            let cx = xi.pow(modulus_minus_one_div(3)).inverse().unwrap();
+            let cy = xi.pow(modulus_minus_one_div(2)).inverse().unwrap();
+            let gen = G2Elem::prime_subgroup_generator();
+            assert!(psi(&gen, &cx, &cy).into_projective() == g2_mul_z(&gen));
+
+            Constants { beta, cx, cy }
+        })
+    }
+
+    fn g1_mul_minus_z_squared(elem: &G1Elem) -> <G1Elem as AffineCurve>::Projective {
+        let z = BigInteger256::from(Z);
+        -elem.mul(z).mul(z)
+    }
+
+    fn g2_mul_z(elem: &G2Elem) -> <G2Elem as AffineCurve>::Projective {
+        -elem.mul(BigInteger256::from(Z))
+    }
+
+
+    // The following assume that the provided point lies on the curve
+
+    pub fn g1_in_subgroup_assuming_on_curve(elem: &G1Elem) -> bool {
+        if elem.is_zero() {
+            return true;
+        }
+        sigma(elem, &constants().beta).into_projective() == g1_mul_minus_z_squared(elem)
+    }
+
+    pub fn g2_in_subgroup_assuming_on_curve(elem: &G2Elem) -> bool {
+        if elem.is_zero() {
+            return true;
+        }
+        let consts = constants();
+        psi(elem, &consts.cx, &consts.cy).into_projective() == g2_mul_z(elem)
+    }
+}
+
+#[cfg(feature = "bn254")]
+mod bn {
+    use crate::{G1Elem, G2Elem};
+
+    pub fn g1_in_subgroup_assuming_on_curve(_elem: &G1Elem) -> bool {
+        true
+    }
+
+    pub fn g2_in_subgroup_assuming_on_curve(elem: &G2Elem) -> bool {
+        elem.is_in_correct_subgroup_assuming_on_curve()
+    }
+}
+
+#[cfg(not(feature = "bn254"))]
+pub use bls::{g1_in_subgroup_assuming_on_curve, g2_in_subgroup_assuming_on_curve};
+#[cfg(feature = "bn254")]
+pub use bn::{g1_in_subgroup_assuming_on_curve, g2_in_subgroup_assuming_on_curve};
+
+
+pub trait SubgroupCheck {
+
+    // Membership in the prime order subgroup, including the curve equation
+    fn in_prime_subgroup(&self) -> bool;
+}
+
+// Implemented on the underlying types, since the aliases G1Elem, G2Elem
+// cannot be told apart by trait coherence
+
+impl SubgroupCheck for GroupAffine<crate::curve::g1::Parameters> {
+    fn in_prime_subgroup(&self) -> bool {
+        self.is_on_curve() && g1_in_subgroup_assuming_on_curve(self)
+    }
+}
+
+impl SubgroupCheck for GroupAffine<crate::curve::g2::Parameters> {
+    fn in_prime_subgroup(&self) -> bool {
+        self.is_on_curve() && g2_in_subgroup_assuming_on_curve(self)
+    }
+}
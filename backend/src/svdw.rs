@@ -0,0 +1,71 @@
+// Shallue-van de Woestijne map to BN254 G1 (y^2 = x^3 + 3), as specified
+// by RFC 9380, Section 6.6.1 with Z = 1, using the straight-line procedure
+// of Appendix F.1. Since A = 0, the simplified SWU map is not applicable;
+// since G1 has cofactor 1, no cofactor clearing is needed afterwards.
+
+use crate::G1Elem;
+use crate::curve::Fq;
+use ark_ff::{BigInteger, Field, One, PrimeField, SquareRootField, Zero};
+use std::sync::OnceLock;
+
+const B: u64 = 3;
+const Z: u64 = 1;
+
+
+struct Constants {
+    c1: Fq,         // g(Z)
+    c2: Fq,         // -Z / 2
+    c3: Fq,         // sqrt(-g(Z) (3 Z^2 + 4 A)), with sgn0(c3) = 0
+    c4: Fq,         // -4 g(Z) / (3 Z^2 + 4 A)
+}
+
+// Parity of the canonical representative
+fn sgn0(elem: &Fq) -> bool {
+    elem.into_repr().is_odd()
+}
+
+fn g(x: &Fq) -> Fq {
+    x.square() * x + Fq::from(B)
+}
+
+fn constants() -> &'static Constants {
+    static CONSTANTS: OnceLock<Constants> = OnceLock::new();
+    CONSTANTS.get_or_init(|| {
+        let z = Fq::from(Z);
+        let c1 = g(&z);
+        let c2 = -z / Fq::from(2u64);
+        let denom = Fq::from(3u64) * z.square();
+        let mut c3 = (-c1 * denom).sqrt().unwrap();
+        if sgn0(&c3) {
+            c3 = -c3;
+        }
+        let c4 = -Fq::from(4u64) * c1 / denom;
+        Constants { c1, c2, c3, c4 }
+    })
+}
+
+pub fn map_to_curve(u: &Fq) -> G1Elem {
+    let Constants { c1, c2, c3, c4 } = constants();
+
+    let tv1 = u.square() * c1;
+    let tv2 = Fq::one() + tv1;
+    let tv1 = Fq::one() - tv1;
+    let tv3 = (tv1 * tv2).inverse().unwrap_or_else(Fq::zero);     // inv0
+    let tv4 = *u * tv1 * tv3 * c3;
+
+    let x1 = *c2 - tv4;
+    let x2 = *c2 + tv4;
+    let x3 = (tv2.square() * tv3).square() * c4 + Fq::from(Z);
+
+    // The first of x1, x2, x3 for which g(x) is a square
+    let x = [x1, x2]
+        .iter()
+        .copied()
+        .find(|x| !g(x).legendre().is_qnr())
+        .unwrap_or(x3);
+    let mut y = g(&x).sqrt().unwrap();
+    if sgn0(u) != sgn0(&y) {
+        y = -y;
+    }
+    G1Elem::new(x, y, false)
+}
@@ -0,0 +1,165 @@
+// Encoding of BLS12-381 points as specified by zcash and adopted by the
+// IETF drafts and Ethereum: big-endian coordinates, where the three most
+// significant bits of the first byte flag compression, infinity and the
+// sign of y respectively. G2 coordinates are encoded as c1 | c0.
+
+use crate::{G1Elem, G2Elem};
+use ark_bls12_381::{Fq, Fq2};
+use ark_ff::{BigInteger, BigInteger384, PrimeField, Zero};
+use std::convert::TryInto;
+
+const FLAG_COMPRESSION: u8  = 0b1000_0000;
+const FLAG_INFINITY: u8     = 0b0100_0000;
+const FLAG_SIGN: u8         = 0b0010_0000;
+const FLAGS: u8             = FLAG_COMPRESSION | FLAG_INFINITY | FLAG_SIGN;
+
+
+fn fq_from_bytes(bytes: &[u8]) -> Option<Fq> {
+    let mut limbs = [0u64; 6];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let end = bytes.len() - 8 * i;
+        *limb = u64::from_be_bytes(bytes[end - 8..end].try_into().unwrap());
+    }
+    Fq::from_repr(BigInteger384(limbs))             // None if not reduced
+}
+
+fn fq_to_bytes(elem: &Fq) -> [u8; 48] {
+    let mut bytes = [0u8; 48];
+    bytes.copy_from_slice(&elem.into_repr().to_bytes_be());
+    bytes
+}
+
+// Lexicographically largest among y, -y
+fn sign_fq(y: &Fq) -> bool {
+    *y > -*y
+}
+
+fn sign_fq2(y: &Fq2) -> bool {
+    *y > -*y
+}
+
+// Splits off the flags of the first byte
+fn split_flags(bytes: &[u8]) -> (u8, Vec<u8>) {
+    let mut body = bytes.to_vec();
+    let flags = body[0] & FLAGS;
+    body[0] &= !FLAGS;
+    (flags, body)
+}
+
+// Infinity must be encoded as all zeros apart from the flags
+fn decode_infinity(flags: u8, body: &[u8], compressed: bool) -> Option<bool> {
+    let infinity = flags & FLAG_INFINITY != 0;
+    if (flags & FLAG_COMPRESSION != 0) != compressed {
+        return None;
+    }
+    if infinity && (flags & FLAG_SIGN != 0 || body.iter().any(|&b| b != 0)) {
+        return None;
+    }
+    if !compressed && flags & FLAG_SIGN != 0 {
+        return None;
+    }
+    Some(infinity)
+}
+
+
+// The following decoders fail for points not on the curve, but do not
+// check membership in the prime order subgroup
+
+pub fn g1_from_compressed(bytes: &[u8; 48]) -> Option<G1Elem> {
+    let (flags, body) = split_flags(bytes);
+    if decode_infinity(flags, &body, true)? {
+        return Some(G1Elem::zero());
+    }
+    let x = fq_from_bytes(&body)?;
+    G1Elem::get_point_from_x(x, flags & FLAG_SIGN != 0)
+}
+
+pub fn g1_from_uncompressed(bytes: &[u8; 96]) -> Option<G1Elem> {
+    let (flags, body) = split_flags(bytes);
+    if decode_infinity(flags, &body, false)? {
+        return Some(G1Elem::zero());
+    }
+    let x = fq_from_bytes(&body[..48])?;
+    let y = fq_from_bytes(&body[48..])?;
+    let elem = G1Elem::new(x, y, false);
+    match elem.is_on_curve() {
+        true    => Some(elem),
+        false   => None,
+    }
+}
+
+pub fn g2_from_compressed(bytes: &[u8; 96]) -> Option<G2Elem> {
+    let (flags, body) = split_flags(bytes);
+    if decode_infinity(flags, &body, true)? {
+        return Some(G2Elem::zero());
+    }
+    let x = Fq2::new(fq_from_bytes(&body[48..])?, fq_from_bytes(&body[..48])?);
+    G2Elem::get_point_from_x(x, flags & FLAG_SIGN != 0)
+}
+
+pub fn g2_from_uncompressed(bytes: &[u8; 192]) -> Option<G2Elem> {
+    let (flags, body) = split_flags(bytes);
+    if decode_infinity(flags, &body, false)? {
+        return Some(G2Elem::zero());
+    }
+    let x = Fq2::new(fq_from_bytes(&body[48..96])?, fq_from_bytes(&body[..48])?);
+    let y = Fq2::new(fq_from_bytes(&body[144..])?, fq_from_bytes(&body[96..144])?);
+    let elem = G2Elem::new(x, y, false);
+    match elem.is_on_curve() {
+        true    => Some(elem),
+        false   => None,
+    }
+}
+
+pub fn g1_to_compressed(elem: &G1Elem) -> [u8; 48] {
+    if elem.is_zero() {
+        let mut bytes = [0u8; 48];
+        bytes[0] = FLAG_COMPRESSION | FLAG_INFINITY;
+        return bytes;
+    }
+    let mut bytes = fq_to_bytes(&elem.x);
+    bytes[0] |= FLAG_COMPRESSION;
+    if sign_fq(&elem.y) {
+        bytes[0] |= FLAG_SIGN;
+    }
+    bytes
+}
+
+pub fn g1_to_uncompressed(elem: &G1Elem) -> [u8; 96] {
+    let mut bytes = [0u8; 96];
+    if elem.is_zero() {
+        bytes[0] = FLAG_INFINITY;
+        return bytes;
+    }
+    bytes[..48].copy_from_slice(&fq_to_bytes(&elem.x));
+    bytes[48..].copy_from_slice(&fq_to_bytes(&elem.y));
+    bytes
+}
+
+pub fn g2_to_compressed(elem: &G2Elem) -> [u8; 96] {
+    let mut bytes = [0u8; 96];
+    if elem.is_zero() {
+        bytes[0] = FLAG_COMPRESSION | FLAG_INFINITY;
+        return bytes;
+    }
+    bytes[..48].copy_from_slice(&fq_to_bytes(&elem.x.c1));
+    bytes[48..].copy_from_slice(&fq_to_bytes(&elem.x.c0));
+    bytes[0] |= FLAG_COMPRESSION;
+    if sign_fq2(&elem.y) {
+        bytes[0] |= FLAG_SIGN;
+    }
+    bytes
+}
+
+pub fn g2_to_uncompressed(elem: &G2Elem) -> [u8; 192] {
+    let mut bytes = [0u8; 192];
+    if elem.is_zero() {
+        bytes[0] = FLAG_INFINITY;
+        return bytes;
+    }
+    bytes[..48].copy_from_slice(&fq_to_bytes(&elem.x.c1));
+    bytes[48..96].copy_from_slice(&fq_to_bytes(&elem.x.c0));
+    bytes[96..144].copy_from_slice(&fq_to_bytes(&elem.y.c1));
+    bytes[144..].copy_from_slice(&fq_to_bytes(&elem.y.c0));
+    bytes
+}
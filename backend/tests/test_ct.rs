@@ -0,0 +1,47 @@
+use backend::*;
+use backend::ct::ConstantTimeEq;
+use ark_ec::{AffineCurve, PairingEngine, ProjectiveCurve};
+use num_traits::identities::{Zero, One};
+
+#[test]
+fn test_ct_scalars() {
+    assert!(ct_eq!(one!(), scalar!(1u64)));
+    assert!(ct_ne!(zero!(), scalar!(1u64)));
+    assert!(ct_eq!(scalar!(7u64) * scalar!(3u64), scalar!(21u64)));
+    assert!(bool::from(scalar!(5u64).ct_eq(&scalar!(5u64))));
+    assert!(bool::from(scalar!(5u64).ct_ne(&scalar!(6u64))));
+}
+
+#[test]
+fn test_ct_g1() {
+    let g = genG1!();
+    let elem = smul1!(scalar!(6u64), g);
+    let other = (g.into_projective().double() + g.into_projective()).double().into_affine();
+    assert!(ct_eq!(elem, other));
+    assert!(ct_ne!(elem, g));
+    assert!(ct_ne!(elem, -elem));
+    assert!(ct_eq!(zeroG1!(), smul1!(zero!(), g)));
+    assert!(ct_ne!(zeroG1!(), g));
+}
+
+#[test]
+fn test_ct_g2() {
+    let h = genG2!();
+    let elem = smul2!(scalar!(6u64), h);
+    let other = (h.into_projective().double() + h.into_projective()).double().into_affine();
+    assert!(ct_eq!(elem, other));
+    assert!(ct_ne!(elem, h));
+    assert!(ct_ne!(elem, -elem));
+    assert!(ct_eq!(zeroG2!(), smul2!(zero!(), h)));
+    assert!(ct_ne!(zeroG2!(), h));
+}
+
+#[test]
+fn test_ct_pairings() {
+    let (g, h) = (genG1!(), genG2!());
+    let left = pair!(smul1!(scalar!(6u64), g), h);
+    let right = pair!(smul1!(scalar!(2u64), g), smul2!(scalar!(3u64), h));
+    assert!(ct_eq!(left, right));
+    assert!(ct_ne!(left, pair!(g, h)));
+    assert!(ct_eq!(pair!(zeroG1!(), h), unit!()));
+}
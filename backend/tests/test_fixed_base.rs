@@ -0,0 +1,58 @@
+use backend::*;
+use backend::fixed_base::{FixedBase, powers};
+
+use ark_ec::{AffineCurve, ProjectiveCurve};
+use ark_ff::Field;
+use ark_std::rand::Rng as ArkRng;
+use num_traits::identities::{Zero, One};
+
+#[test]
+fn test_fixed_base_mul1() {
+    let mut rng = ::util::snarky_rng();
+    let base = smul1!(rscalar!(rng), genG1!());
+    for &nr_scalars in [1, 31, 32, 1000].iter() {
+        let table = FixedBase::new(&base, nr_scalars);
+        let mut scalars = (0..10)
+            .map(|_| rscalar!(rng))
+            .collect::<Vec<Scalar>>();
+        scalars.extend_from_slice(&[zero!(), one!(), -one!()]);
+        let expected = scalars
+            .iter()
+            .map(|&s| smul1!(s, base))
+            .collect::<Vec<G1Elem>>();
+        assert_eq!(table.batch_mul(&scalars), expected);
+        assert_eq!(table.mul(&scalars[0]).into_affine(), expected[0]);
+    }
+}
+
+#[test]
+fn test_fixed_base_mul2() {
+    let mut rng = ::util::snarky_rng();
+    let base = genG2!();
+    let table = FixedBase::new(&base, 100);
+    let scalars = (0..10)
+        .map(|_| rscalar!(rng))
+        .collect::<Vec<Scalar>>();
+    let expected = scalars
+        .iter()
+        .map(|&s| smul2!(s, base))
+        .collect::<Vec<G2Elem>>();
+    assert_eq!(table.batch_mul(&scalars), expected);
+}
+
+#[test]
+fn test_fixed_base_zero() {
+    let table = FixedBase::new(&zeroG1!(), 10);
+    assert_eq!(table.batch_mul(&[scalar!(7u64)]), vec![zeroG1!()]);
+}
+
+#[test]
+fn test_powers() {
+    let x = scalar!(3u64);
+    assert_eq!(powers(x, 0), vec![]);
+    assert_eq!(powers(x, 1), vec![one!()]);
+    let out = powers(x, 10);
+    for (i, &p) in out.iter().enumerate() {
+        assert_eq!(p, pow!(x, i));
+    }
+}
@@ -9,7 +9,7 @@ use ark_ff::ToBytes;
 #[test]
 fn test_genG1() {
     assert_eq!(
-        ::ark_bls12_381::G1Affine::prime_subgroup_generator(),
+        backend::curve::G1Affine::prime_subgroup_generator(),
         genG1!()
     );
 }
@@ -17,7 +17,7 @@ fn test_genG1() {
 #[test]
 fn test_genG2() {
     assert_eq!(
-        ::ark_bls12_381::G2Affine::prime_subgroup_generator(),
+        backend::curve::G2Affine::prime_subgroup_generator(),
         genG2!()
     );
 }
@@ -25,7 +25,7 @@ fn test_genG2() {
 #[test]
 fn test_zeroG1() {
     assert_eq!(
-        ::ark_bls12_381::G1Affine::zero(),
+        backend::curve::G1Affine::zero(),
         zeroG1!()
     );
 }
@@ -33,7 +33,7 @@ fn test_zeroG1() {
 #[test]
 fn test_zeroG2() {
     assert_eq!(
-        ::ark_bls12_381::G2Affine::zero(),
+        backend::curve::G2Affine::zero(),
         zeroG2!()
     );
 }
@@ -52,19 +52,22 @@ fn test_contained_in_group() {
     }
 }
 
+// Byte size of base field elements
+const FQ: usize = if cfg!(feature = "bn254") { 32 } else { 48 };
+
 #[test]
 fn test_bytes1() {
-    let mut expected: [u8; 97] = [0; 97];
-    expected[48] = 1;
-    expected[96] = 1;
+    let mut expected = vec![0u8; 2 * FQ + 1];
+    expected[FQ] = 1;
+    expected[2 * FQ] = 1;
     assert!(bytes1!(zeroG1!()) == expected);
 }
 
 #[test]
 fn test_bytes2() {
-    let mut expected: [u8; 193] = [0; 193];
-    expected[96]  = 1;
-    expected[192] = 1;
+    let mut expected = vec![0u8; 4 * FQ + 1];
+    expected[2 * FQ] = 1;
+    expected[4 * FQ] = 1;
     assert!(bytes2!(zeroG2!()) == expected);
 }
 
@@ -80,3 +83,27 @@ fn test_ct_comparisons() {
     assert!(!ct_eq!(elm1, elm3));
     assert!(!ct_ne!(elm1, elm2));
 }
+
+#[test]
+fn test_write_read() {
+    let f = scalar!(666u64);
+    let elem1 = smul1!(f, genG1!());
+    let elem2 = smul2!(f, genG2!());
+    for &(compressed, size1, size2) in [(true, FQ, 2 * FQ), (false, 2 * FQ, 4 * FQ)].iter() {
+        let (mut buffer1, mut buffer2) = (Vec::<u8>::new(), Vec::<u8>::new());
+        write1!(&mut buffer1, elem1, compressed).unwrap();
+        write2!(&mut buffer2, elem2, compressed).unwrap();
+        assert_eq!(buffer1.len(), size1);
+        assert_eq!(buffer2.len(), size2);
+        assert_eq!(read1!(&buffer1[..], compressed).unwrap(), elem1);
+        assert_eq!(read2!(&buffer2[..], compressed).unwrap(), elem2);
+    }
+}
+
+#[test]
+fn test_read_off_curve() {
+    let mut buffer = Vec::<u8>::new();
+    write1!(&mut buffer, genG1!(), false).unwrap();
+    buffer[0] ^= 1;
+    assert!(read1!(&buffer[..], false).is_err());
+}
@@ -0,0 +1,114 @@
+use backend::*;
+use backend::hash_to_curve::{dst_g1, expand_message_xmd, hash_to_g1, SUITE_G1};
+use util::HashError;
+
+// RFC 9380, Appendix J.9.1 (BLS12-381); gnark test vectors (BN254)
+const DST: &[u8] = if cfg!(feature = "bn254") {
+    b"QUUX-V01-CS02-with-BN254G1_XMD:SHA-256_SVDW_RO_"
+} else {
+    b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_"
+};
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_expand_message_xmd_vectors() {
+    // RFC 9380, Appendix K.1
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    let parametrization: [(&[u8], usize, &str); 4] = [
+        (b"", 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+        (b"abc", 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+        (b"abcdef0123456789", 0x20, "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1"),
+        (
+            b"",
+            0x80,
+            "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+        ),
+    ];
+    for (msg, len, expected) in parametrization.iter() {
+        assert_eq!(expand_message_xmd(msg, dst, *len).unwrap(), from_hex(expected));
+    }
+}
+
+#[test]
+#[cfg(not(feature = "bn254"))]
+fn test_hash_to_g1_vectors() {
+    use backend::zcash::g1_to_uncompressed;
+
+    let parametrization: [(&[u8], &str, &str); 3] = [
+        (
+            b"",
+            "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+            "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+        ),
+        (
+            b"abc",
+            "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903",
+            "0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+        ),
+        (
+            b"abcdef0123456789",
+            "11e0b079dea29a68f0383ee94fed1b940995272407e3bb916bbf268c263ddd57a6a27200a784cbc248e84f357ce82d98",
+            "03a87ae2caf14e8ee52e51fa2ed8eefe80f02457004ba4d486d6aa1f517c0889501dc7413753f9599b099ebcbbd2d709",
+        ),
+    ];
+    for (msg, x, y) in parametrization.iter() {
+        let elem = hash_to_g1(msg, DST).unwrap();
+        let expected = [from_hex(x), from_hex(y)].concat();
+        assert_eq!(g1_to_uncompressed(&elem).to_vec(), expected);
+        assert!(elem.is_on_curve());
+        assert!(elem.is_in_correct_subgroup_assuming_on_curve());
+    }
+}
+
+#[test]
+#[cfg(feature = "bn254")]
+fn test_hash_to_g1_vectors() {
+    use ark_ff::{BigInteger, PrimeField};
+
+    let parametrization: [(&[u8], &str, &str); 3] = [
+        (
+            b"",
+            "0a976ab906170db1f9638d376514dbf8c42aef256a54bbd48521f20749e59e86",
+            "02925ead66b9e68bfc309b014398640ab55f6619ab59bc1fab2210ad4c4d53d5",
+        ),
+        (
+            b"abc",
+            "23f717bee89b1003957139f193e6be7da1df5f1374b26a4643b0378b5baf53d1",
+            "04142f826b71ee574452dbc47e05bc3e1a647478403a7ba38b7b93948f4e151d",
+        ),
+        (
+            b"abcdef0123456789",
+            "187dbf1c3c89aceceef254d6548d7163fdfa43084145f92c4c91c85c21442d4a",
+            "0abd99d5b0000910b56058f9cc3b0ab0a22d47cf27615f588924fac1e5c63b4d",
+        ),
+    ];
+    for (msg, x, y) in parametrization.iter() {
+        let elem = hash_to_g1(msg, DST).unwrap();
+        assert_eq!(elem.x.into_repr().to_bytes_be(), from_hex(x));
+        assert_eq!(elem.y.into_repr().to_bytes_be(), from_hex(y));
+        assert!(elem.is_on_curve());
+    }
+}
+
+#[test]
+fn test_hash_macro() {
+    let bytes: Vec<u8> = (0..5).collect();
+    let dst = dst_g1("SNARKY-V01-CS01-TEST");
+    assert_eq!(dst, [b"SNARKY-V01-CS01-TEST-with-", SUITE_G1].concat());
+    assert_eq!(hashG1!(&bytes, &dst), hash_to_g1(&bytes, &dst));
+    assert_ne!(hashG1!(&bytes, &dst), hashG1!(&bytes, DST));
+    assert_ne!(hashG1!(&bytes, &dst), hashG1!(&bytes[..4], &dst));
+}
+
+#[test]
+fn test_invalid_dst() {
+    assert_eq!(hash_to_g1(b"abc", b"").unwrap_err(), HashError::EmptyDst);
+    assert_eq!(hash_to_g1(b"abc", &[0x41; 256]).unwrap_err(), HashError::DstTooLong);
+    assert_eq!(expand_message_xmd(b"abc", DST, 255 * 32 + 1).unwrap_err(), HashError::LengthTooLarge);
+}
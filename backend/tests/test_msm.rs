@@ -0,0 +1,63 @@
+use backend::*;
+
+use ark_ec::AffineCurve;
+use ark_std::rand::Rng as ArkRng;
+use num_traits::identities::{Zero, One};
+
+fn naive1(bases: &[G1Elem], scalars: &[Scalar]) -> G1Elem {
+    bases.iter()
+        .zip(scalars.iter())
+        .fold(zeroG1!(), |acc, (&base, &s)| add1!(acc, smul1!(s, base)))
+}
+
+fn naive2(bases: &[G2Elem], scalars: &[Scalar]) -> G2Elem {
+    bases.iter()
+        .zip(scalars.iter())
+        .fold(zeroG2!(), |acc, (&base, &s)| add2!(acc, smul2!(s, base)))
+}
+
+#[test]
+fn test_msm1() {
+    let mut rng = ::util::snarky_rng();
+    for &len in [0, 1, 2, 5, 31, 32, 100, 300].iter() {
+        let bases = (0..len)
+            .map(|_| smul1!(rscalar!(rng), genG1!()))
+            .collect::<Vec<G1Elem>>();
+        let scalars = (0..len)
+            .map(|_| rscalar!(rng))
+            .collect::<Vec<Scalar>>();
+        assert_eq!(msm1!(bases, scalars), naive1(&bases, &scalars));
+    }
+}
+
+#[test]
+fn test_msm2() {
+    let mut rng = ::util::snarky_rng();
+    for &len in [0, 1, 2, 5, 31, 32, 100].iter() {
+        let bases = (0..len)
+            .map(|_| smul2!(rscalar!(rng), genG2!()))
+            .collect::<Vec<G2Elem>>();
+        let scalars = (0..len)
+            .map(|_| rscalar!(rng))
+            .collect::<Vec<Scalar>>();
+        assert_eq!(msm2!(bases, scalars), naive2(&bases, &scalars));
+    }
+}
+
+#[test]
+fn test_msm_edge_scalars() {
+    let g = genG1!();
+    let bases = vec![g, smul1!(scalar!(2u64), g), zeroG1!(), g];
+    let scalars = vec![zero!(), one!(), scalar!(7u64), -one!()];
+    assert_eq!(msm1!(bases, scalars), naive1(&bases, &scalars));
+    assert_eq!(msm1!(bases, scalars), smul1!(scalar!(1u64), g));
+}
+
+#[test]
+fn test_msm_unequal_lengths() {
+    let g = genG1!();
+    let bases = vec![g, g, g];
+    let scalars = vec![one!(), one!()];
+    assert_eq!(msm1!(bases, scalars), smul1!(scalar!(2u64), g));
+    assert_eq!(msm1!(bases[..1], scalars), g);
+}
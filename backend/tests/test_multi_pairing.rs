@@ -0,0 +1,64 @@
+use backend::*;
+use backend::multi_pairing::{multi_pairing, multi_pairing_eq};
+
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_std::rand::Rng as ArkRng;
+use num_traits::identities::{Zero, One};
+
+#[test]
+fn test_multi_pairing() {
+    let mut rng = ::util::snarky_rng();
+    for &len in [0, 1, 2, 15, 16, 17, 40].iter() {
+        let pairs = (0..len)
+            .map(|_| (
+                smul1!(rscalar!(rng), genG1!()),
+                smul2!(rscalar!(rng), genG2!()),
+            ))
+            .collect::<Vec<(G1Elem, G2Elem)>>();
+        let expected = pairs
+            .iter()
+            .fold(unit!(), |acc, &(p, q)| acc * pair!(p, q));
+        assert_eq!(multi_pairing(&pairs), Some(expected));
+    }
+}
+
+#[test]
+fn test_multi_pairing_eq() {
+    let (g, h) = (genG1!(), genG2!());
+    let (a, b) = (scalar!(7u64), scalar!(11u64));
+    let (ga, hb) = (smul1!(a, g), smul2!(b, h));
+    let gab = smul1!(a * b, g);
+
+    // e(aG, bH) = e(abG, H)
+    assert!(multi_pairing_eq!((ga, hb), (-gab, h)));
+    assert!(multi_pairing_eq!((ga, hb), (-gab, h),));
+    assert!(!multi_pairing_eq!((ga, hb), (gab, h)));
+    assert!(!multi_pairing_eq!((ga, hb), (-ga, h)));
+
+    // Trivial products
+    assert!(multi_pairing_eq(&[]));
+    assert!(multi_pairing_eq!((zeroG1!(), h)));
+    assert!(multi_pairing_eq!((g, zeroG2!())));
+    assert!(!multi_pairing_eq!((g, h)));
+}
+
+#[test]
+fn test_multi_pairing_eq_batched() {
+    use backend::multi_pairing::{Equation, multi_pairing_eq_batched};
+
+    let mut rng = ::util::snarky_rng();
+    let (g, h) = (genG1!(), genG2!());
+    let (a, b) = (scalar!(7u64), scalar!(11u64));
+    let mut equations: Vec<Equation> = vec![
+        vec![(smul1!(a, g), smul2!(b, h)), (-smul1!(a * b, g), h)],
+        vec![(smul1!(b, g), h), (-g, smul2!(b, h))],
+        vec![],
+    ];
+    let r = (0..3).map(|_| rscalar!(rng)).collect::<Vec<Scalar>>();
+    assert!(multi_pairing_eq_batched(&equations, &r));
+    assert!(multi_pairing_eq_batched(&[], &[]));
+    assert!(!multi_pairing_eq_batched(&equations, &r[..2]));
+
+    equations[1][0].0 = smul1!(a, g);
+    assert!(!multi_pairing_eq_batched(&equations, &r));
+}
@@ -50,13 +50,13 @@ fn test_smul1() {
         let b = smul1!(scalar!(f2), a);         // f2 * f1 * G
 
         assert_eq!(
-            ::ark_bls12_381::G1Affine::from(
-                ::ark_bls12_381::G1Affine::prime_subgroup_generator().mul(scalar!(f1))
+            backend::curve::G1Affine::from(
+                backend::curve::G1Affine::prime_subgroup_generator().mul(scalar!(f1))
             ),
             a
         );
         assert_eq!(
-            ::ark_bls12_381::G1Affine::from(a.mul(scalar!(f2))),
+            backend::curve::G1Affine::from(a.mul(scalar!(f2))),
             b
         );
     }
@@ -78,13 +78,13 @@ fn test_smul2() {
         let b = smul2!(scalar!(f2), a);         // f2 * f1 * H
 
         assert_eq!(
-            ::ark_bls12_381::G2Affine::from(
-                ::ark_bls12_381::G2Affine::prime_subgroup_generator().mul(scalar!(f1))
+            backend::curve::G2Affine::from(
+                backend::curve::G2Affine::prime_subgroup_generator().mul(scalar!(f1))
             ),
             a
         );
         assert_eq!(
-            ::ark_bls12_381::G2Affine::from(a.mul(scalar!(f2))),
+            backend::curve::G2Affine::from(a.mul(scalar!(f2))),
             b
         );
     }
@@ -102,7 +102,7 @@ fn test_pair() {
         let left  = smul1!(scalar!(f1), genG1!());
         let right = smul2!(scalar!(f2), genG2!());
         assert_eq!(
-            ::ark_bls12_381::Bls12_381::pairing(left, right),
+            backend::curve::Engine::pairing(left, right),
             pair!(left, right)
         );
     }
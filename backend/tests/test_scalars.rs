@@ -10,20 +10,20 @@ use ark_ff::fields::Field;
 fn test_scalar() {
     let parametrization = [0u64, 1, 2, 10, 666];
     for &num in &parametrization {
-        assert_eq!(::ark_bls12_381::Fr::from(num), scalar!(num));
+        assert_eq!(backend::curve::Fr::from(num), scalar!(num));
     }
 }
 
 #[test]
 fn test_zero() {
-    assert_eq!(::ark_bls12_381::Fr::from(0u64), zero!());
-    assert_ne!(::ark_bls12_381::Fr::from(1u64), zero!());
+    assert_eq!(backend::curve::Fr::from(0u64), zero!());
+    assert_ne!(backend::curve::Fr::from(1u64), zero!());
 }
 
 #[test]
 fn test_one() {
-    assert_eq!(::ark_bls12_381::Fr::from(1u64), one!());
-    assert_ne!(::ark_bls12_381::Fr::from(0u64), one!());
+    assert_eq!(backend::curve::Fr::from(1u64), one!());
+    assert_ne!(backend::curve::Fr::from(0u64), one!());
 }
 
 #[test]
@@ -0,0 +1,76 @@
+use backend::*;
+use backend::subgroup::{
+    g1_in_subgroup_assuming_on_curve,
+    g2_in_subgroup_assuming_on_curve,
+};
+use backend::curve::{Fq, Fq2};
+use ark_ec::AffineCurve;
+use ark_std::rand::Rng as ArkRng;
+use num_traits::identities::Zero;
+
+// Curve points with small x-coordinates, mostly outside the subgroup
+fn g1_points() -> Vec<G1Elem> {
+    (1u64..40)
+        .filter_map(|x| G1Elem::get_point_from_x(Fq::from(x), false))
+        .collect()
+}
+
+fn g2_points() -> Vec<G2Elem> {
+    (1u64..40)
+        .filter_map(|x| G2Elem::get_point_from_x(Fq2::new(Fq::from(x), Fq::from(1u64)), true))
+        .collect()
+}
+
+#[test]
+fn test_g1_membership() {
+    let mut rng = ::util::snarky_rng();
+    for _ in 0..5 {
+        let elem = smul1!(rscalar!(rng), genG1!());
+        assert!(contained_in_group!(elem));
+    }
+    assert!(contained_in_group!(genG1!()));
+    assert!(contained_in_group!(zeroG1!()));
+
+    let points = g1_points();
+    assert!(points.len() > 10);
+    let mut outside = 0;
+    for elem in points.iter() {
+        let expected = elem.is_in_correct_subgroup_assuming_on_curve();
+        assert_eq!(g1_in_subgroup_assuming_on_curve(elem), expected);
+        assert_eq!(contained_in_group!(elem), expected);
+        assert!(contained_in_group!(elem.mul_by_cofactor()));
+        outside += !expected as usize;
+    }
+    assert!(outside > 0 || cfg!(feature = "bn254"));     // BN254 G1 has cofactor 1
+}
+
+#[test]
+fn test_g2_membership() {
+    let mut rng = ::util::snarky_rng();
+    for _ in 0..5 {
+        let elem = smul2!(rscalar!(rng), genG2!());
+        assert!(contained_in_group!(elem));
+    }
+    assert!(contained_in_group!(genG2!()));
+    assert!(contained_in_group!(zeroG2!()));
+
+    let points = g2_points();
+    assert!(points.len() > 10);
+    let mut outside = 0;
+    for elem in points.iter() {
+        let expected = elem.is_in_correct_subgroup_assuming_on_curve();
+        assert_eq!(g2_in_subgroup_assuming_on_curve(elem), expected);
+        assert_eq!(contained_in_group!(elem), expected);
+        assert!(contained_in_group!(elem.mul_by_cofactor()));
+        outside += !expected as usize;
+    }
+    assert!(outside > 0);
+}
+
+#[test]
+fn test_off_curve() {
+    let elem = G1Elem::new(Fq::from(1u64), Fq::from(1u64), false);
+    assert!(!contained_in_group!(elem));
+    let elem = G2Elem::new(Fq2::new(Fq::from(1u64), Fq::zero()), Fq2::new(Fq::from(1u64), Fq::zero()), false);
+    assert!(!contained_in_group!(elem));
+}
@@ -0,0 +1,65 @@
+// The zcash encoding is specific to BLS12-381
+#![cfg(not(feature = "bn254"))]
+
+use backend::*;
+use backend::zcash::*;
+use ark_ec::AffineCurve;
+use num_traits::identities::Zero;
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+const GEN_G1: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+const GEN_G2: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+#[test]
+fn test_generators_compressed() {
+    assert_eq!(g1_to_compressed(&genG1!()).to_vec(), from_hex(GEN_G1));
+    assert_eq!(g2_to_compressed(&genG2!()).to_vec(), from_hex(GEN_G2));
+}
+
+#[test]
+fn test_zcash_roundtrip() {
+    let parametrization = [0u64, 1, 2, 7, 666, 389473847];
+    for &factor in &parametrization {
+        let elem1 = smul1!(scalar!(factor), genG1!());
+        let elem2 = smul2!(scalar!(factor), genG2!());
+        let minus1 = -elem1;
+        let minus2 = -elem2;
+        for &(e1, e2) in [(elem1, elem2), (minus1, minus2)].iter() {
+            assert_eq!(g1_from_compressed(&g1_to_compressed(&e1)), Some(e1));
+            assert_eq!(g1_from_uncompressed(&g1_to_uncompressed(&e1)), Some(e1));
+            assert_eq!(g2_from_compressed(&g2_to_compressed(&e2)), Some(e2));
+            assert_eq!(g2_from_uncompressed(&g2_to_uncompressed(&e2)), Some(e2));
+        }
+    }
+}
+
+#[test]
+fn test_zcash_infinity() {
+    let mut bytes = [0u8; 48];
+    bytes[0] = 0xc0;
+    assert_eq!(g1_to_compressed(&zeroG1!()), bytes);
+    assert_eq!(g1_from_compressed(&bytes), Some(zeroG1!()));
+    bytes[47] = 1;
+    assert_eq!(g1_from_compressed(&bytes), None);
+}
+
+#[test]
+fn test_zcash_invalid_input() {
+    let mut bytes = g1_to_compressed(&genG1!());
+    bytes[0] &= 0x7f;                                   // clear compression flag
+    assert_eq!(g1_from_compressed(&bytes), None);
+
+    let mut bytes = g1_to_uncompressed(&genG1!());
+    bytes[95] ^= 1;                                     // off the curve
+    assert_eq!(g1_from_uncompressed(&bytes), None);
+
+    let mut bytes = [0xffu8; 48];                       // x not reduced
+    bytes[0] = 0x9f;
+    assert_eq!(g1_from_compressed(&bytes), None);
+}
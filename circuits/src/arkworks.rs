@@ -0,0 +1,58 @@
+// Adapter from arkworks circuits (`ark_relations::r1cs::ConstraintSynthesizer`)
+// to constraint systems. The circuit is synthesized in setup mode, so that
+// no witness needs to be provided. Arkworks places the constant 1 and the
+// instance variables before the witness variables, so that the latter
+// occupy the variables l + 1, ..., m.
+
+use backend::Scalar as F;
+use util::{SnarkyError, CircuitError};
+use crate::{ConstraintSystem, R1CS, Row};
+
+use ark_relations::r1cs::{
+    ConstraintSynthesizer,
+    ConstraintSystem as ArkConstraintSystem,
+    Matrix,
+    SynthesisMode,
+};
+
+fn synthesis_error(cause: &str) -> SnarkyError {
+    CircuitError::Synthesis(cause.to_string()).into()
+}
+
+fn convert(matrix: Matrix<F>) -> Vec<Row> {
+    matrix
+        .into_iter()
+        .map(|row| row.into_iter().map(|(coeff, i)| (i, coeff)).collect())
+        .collect()
+}
+
+pub fn synthesize_r1cs<C: ConstraintSynthesizer<F>>(circuit: C) -> Result<R1CS, SnarkyError> {
+    let cs = ArkConstraintSystem::<F>::new_ref();
+    cs.set_mode(SynthesisMode::Setup);
+    if let Err(err) = circuit.generate_constraints(cs.clone()) {
+        return Err(synthesis_error(&err.to_string()))
+    }
+    cs.finalize();
+    let matrices = match cs.to_matrices() {
+        Some(matrices) => matrices,
+        None => {
+            return Err(synthesis_error("Matrices not constructed"))
+        }
+    };
+
+    let l = matrices.num_instance_variables - 1;
+    let m = l + matrices.num_witness_variables;
+    R1CS::create(
+        convert(matrices.a),
+        convert(matrices.b),
+        convert(matrices.c),
+        m,
+        l,
+    )
+}
+
+// QAP of an arkworks circuit, with its instance variables occupying
+// the variables 1, ..., l
+pub fn synthesize<C: ConstraintSynthesizer<F>>(circuit: C) -> Result<ConstraintSystem, SnarkyError> {
+    synthesize_r1cs(circuit)?.to_qap()
+}
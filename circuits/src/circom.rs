@@ -0,0 +1,223 @@
+// Parser of circom binary constraint files (`.r1cs`) over the scalar field
+// of the backend curve.
+//
+// Layout: magic `r1cs` | version (u32) | number of sections (u32), followed
+// by sections of the form type (u32) | size (u64) | data. Integers and field
+// elements are little-endian, the latter in standard (non-Montgomery) form.
+//
+// Circom orders wires as the constant 1, the public outputs, the public
+// inputs and the private signals, so that the public ones fall onto the
+// variables 1, ..., l with l = nPubOut + nPubIn.
+
+use backend::Scalar as F;
+use util::{SnarkyError, CircuitError};
+use crate::{ConstraintSystem, R1CS, Row};
+
+use ark_ff::{BigInteger256, FpParameters, PrimeField};
+
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::io::{Read, Seek, SeekFrom};
+
+pub const MAGIC: [u8; 4] = *b"r1cs";
+pub const VERSION: u32 = 1;
+
+pub const SECTION_HEADER: u32 = 1;
+pub const SECTION_CONSTRAINTS: u32 = 2;
+pub const SECTION_WIRE_TO_LABEL: u32 = 3;
+
+const N8: usize = 32;                   // Byte size of scalar field elements
+
+
+// Header section of a .r1cs file
+#[derive(Debug, PartialEq)]
+pub struct CircomHeader {
+    pub nr_wires: u32,
+    pub nr_pub_out: u32,
+    pub nr_pub_in: u32,
+    pub nr_prv_in: u32,
+    pub nr_labels: u64,
+    pub nr_constraints: u32,
+}
+
+impl CircomHeader {
+
+    // Saturating, so that crafted headers cannot overflow
+    pub fn shape(&self) -> (usize, usize) {
+        let m = (self.nr_wires as usize).saturating_sub(1);
+        let l = (self.nr_pub_out as usize).saturating_add(self.nr_pub_in as usize);
+        (m, l)
+    }
+}
+
+
+fn format_error(cause: &'static str) -> SnarkyError {
+    CircuitError::InvalidFormat(cause).into()
+}
+
+fn read_bytes<R: Read>(reader: &mut R, buffer: &mut [u8]) -> Result<(), SnarkyError> {
+    reader.read_exact(buffer).map_err(|_| {
+        format_error("Unexpected end of input")
+    })
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, SnarkyError> {
+    let mut buffer = [0u8; 4];
+    read_bytes(reader, &mut buffer)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, SnarkyError> {
+    let mut buffer = [0u8; 8];
+    read_bytes(reader, &mut buffer)?;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+fn read_scalar<R: Read>(reader: &mut R) -> Result<F, SnarkyError> {
+    let mut buffer = [0u8; N8];
+    read_bytes(reader, &mut buffer)?;
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        *limb = u64::from_le_bytes(buffer[8 * i..8 * i + 8].try_into().unwrap());
+    }
+    match F::from_repr(BigInteger256(limbs)) {
+        Some(elem) => Ok(elem),
+        None => {
+            Err(format_error("Detected unreduced coefficient"))
+        }
+    }
+}
+
+fn seek<R: Seek>(reader: &mut R, pos: SeekFrom) -> Result<u64, SnarkyError> {
+    reader.seek(pos).map_err(|_| {
+        format_error("Could not seek")
+    })
+}
+
+
+// Maps section types to (offset, size) pairs
+fn read_sections<R: Read + Seek>(reader: &mut R)
+    -> Result<HashMap<u32, (u64, u64)>, SnarkyError>
+{
+    let mut magic = [0u8; 4];
+    read_bytes(reader, &mut magic)?;
+    if magic != MAGIC || read_u32(reader)? != VERSION {
+        return Err(format_error("Invalid magic or version"))
+    }
+    let nr_sections = read_u32(reader)?;
+    let mut sections = HashMap::new();
+    for _ in 0..nr_sections {
+        let kind = read_u32(reader)?;
+        let size = read_u64(reader)?;
+        let offset = seek(reader, SeekFrom::Current(0))?;
+        if sections.insert(kind, (offset, size)).is_some() {
+            return Err(format_error("Detected duplicate section"))
+        }
+        let skip = i64::try_from(size).map_err(|_| {
+            format_error("Detected oversized section")
+        })?;
+        seek(reader, SeekFrom::Current(skip))?;
+    }
+    let end = seek(reader, SeekFrom::End(0))?;
+    if sections.values().any(|&(offset, size)| offset.checked_add(size).is_none_or(|e| e > end)) {
+        return Err(format_error("Detected truncated section"))
+    }
+    Ok(sections)
+}
+
+fn seek_section<R: Read + Seek>(
+    reader: &mut R,
+    sections: &HashMap<u32, (u64, u64)>,
+    kind: u32,
+) -> Result<u64, SnarkyError> {
+    match sections.get(&kind) {
+        Some(&(offset, size)) => {
+            seek(reader, SeekFrom::Start(offset))?;
+            Ok(size)
+        },
+        None => {
+            Err(format_error("Missing section"))
+        }
+    }
+}
+
+fn parse_header<R: Read + Seek>(reader: &mut R, sections: &HashMap<u32, (u64, u64)>)
+    -> Result<CircomHeader, SnarkyError>
+{
+    seek_section(reader, sections, SECTION_HEADER)?;
+    if read_u32(reader)? as usize != N8 {         // Checked before reading the prime
+        return Err(CircuitError::FieldMismatch.into())
+    }
+    let mut buffer = [0u8; N8];
+    read_bytes(reader, &mut buffer)?;
+    let modulus = <F as PrimeField>::Params::MODULUS.0
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes().to_vec())
+        .collect::<Vec<u8>>();
+    if buffer[..] != modulus[..] {
+        return Err(CircuitError::FieldMismatch.into())
+    }
+    let header = CircomHeader {
+        nr_wires: read_u32(reader)?,
+        nr_pub_out: read_u32(reader)?,
+        nr_pub_in: read_u32(reader)?,
+        nr_prv_in: read_u32(reader)?,
+        nr_labels: read_u64(reader)?,
+        nr_constraints: read_u32(reader)?,
+    };
+    let nr_public = header.nr_pub_out as u64 + header.nr_pub_in as u64;
+    if header.nr_wires == 0 || nr_public >= header.nr_wires as u64 {
+        return Err(format_error("Inconsistent numbers of wires"))
+    }
+    Ok(header)
+}
+
+// nNonZero (u32) | (wireId (u32) | coefficient) pairs
+fn read_row<R: Read>(reader: &mut R, header: &CircomHeader)
+    -> Result<Row, SnarkyError>
+{
+    let nr_terms = read_u32(reader)?;
+    let mut row = Row::new();
+    for _ in 0..nr_terms {
+        let wire = read_u32(reader)?;
+        if wire >= header.nr_wires {
+            return Err(format_error("Detected wire out of range"))
+        }
+        row.push((wire as usize, read_scalar(reader)?));
+    }
+    Ok(row)
+}
+
+
+pub fn read_header<R: Read + Seek>(reader: &mut R) -> Result<CircomHeader, SnarkyError> {
+    let sections = read_sections(reader)?;
+    parse_header(reader, &sections)
+}
+
+pub fn read_r1cs<R: Read + Seek>(reader: &mut R) -> Result<R1CS, SnarkyError> {
+    let sections = read_sections(reader)?;
+    let header = parse_header(reader, &sections)?;
+
+    let size = seek_section(reader, &sections, SECTION_CONSTRAINTS)?;
+    let mut a = Vec::<Row>::new();
+    let mut b = Vec::<Row>::new();
+    let mut c = Vec::<Row>::new();
+    for _ in 0..header.nr_constraints {
+        a.push(read_row(reader, &header)?);
+        b.push(read_row(reader, &header)?);
+        c.push(read_row(reader, &header)?);
+    }
+    let (offset, _) = sections[&SECTION_CONSTRAINTS];
+    if seek(reader, SeekFrom::Current(0))? != offset + size {
+        return Err(format_error("Constraints section size mismatch"))
+    }
+
+    let (m, l) = header.shape();
+    R1CS::create(a, b, c, m, l)
+}
+
+// Loads the QAP of a circom circuit, with its public outputs and inputs
+// occupying the variables 1, ..., l
+pub fn load<R: Read + Seek>(reader: &mut R) -> Result<ConstraintSystem, SnarkyError> {
+    read_r1cs(reader)?.to_qap()
+}
@@ -1,6 +1,11 @@
 use backend::Scalar as F;
 use polynomials::Univariate;
-use util::SnarkyError;
+use util::{SnarkyError, ShapeError};
+
+mod r1cs;
+pub use r1cs::{R1CS, Row};
+pub mod circom;
+pub mod arkworks;
 
 #[derive(Debug, PartialEq)]
 pub struct ConstraintSystem {
@@ -22,35 +27,23 @@ impl ConstraintSystem {
         t: Univariate<F>,
         l: usize
     ) -> Result<Self, SnarkyError> {
+        if u.is_empty() || t.degree() < 1 {
+            return Err(ShapeError::EmptyCircuit.into())
+        }
+        if t.degree() < 2 {
+            return Err(ShapeError::TooFewConstraints.into())
+        }
         let m = u.len() - 1;
         if v.len() != m + 1 || w.len() != m + 1 {
-            let line = line!() - 1;
-            return Err(SnarkyError::create("Could not create QAP",
-                "Unequal lengths for u, v, w",
-                file!(),
-                line,
-                101
-            ))
+            return Err(ShapeError::UnequalLengths.into())
         } else if l + 1 > m {
-            let line = line!() - 1;
-            return Err(SnarkyError::create("Could not create QAP", 
-                "l is not < m",
-                file!(),
-                line,
-                102
-            ))
+            return Err(ShapeError::InputsOutOfRange.into())
         } else {
             let n = t.degree() as usize;
             for p in [&u, &v, &w].iter() {
                 for i in 0..m + 1 {
                     if p[i].degree() as usize != n - 1 {
-                        let line = line!() - 1;
-                        return Err(SnarkyError::create("Could not create QAP", 
-                            "Detected degree unequal to n-1",
-                            file!(),
-                            line,
-                            103,
-                        ))
+                        return Err(ShapeError::DegreeMismatch.into())
                     }
                 }
             }
@@ -59,6 +52,9 @@ impl ConstraintSystem {
     }
 
     pub fn create_default(m: usize, n: usize, l: usize) -> Result<Self, SnarkyError> {
+        if n == 0 {
+            return Err(ShapeError::EmptyCircuit.into())
+        }
 
         let mut coeffs1 = vec![1];
         coeffs1.append(&mut vec![0; n - 1]);    // [1] + (n - 1) * [0]
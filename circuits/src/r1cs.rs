@@ -0,0 +1,102 @@
+use backend::Scalar as F;
+use polynomials::Univariate;
+use util::{SnarkyError, ShapeError};
+use crate::ConstraintSystem;
+
+use ark_ff::{Zero, One};
+
+// Sparse row of a constraint matrix: (variable index, coefficient) pairs
+pub type Row = Vec<(usize, F)>;
+
+// Rank-1 constraint system <A_k, z> * <B_k, z> = <C_k, z>, where z is an
+// assignment of the variables 0, ..., m with z_0 = 1 and z_1, ..., z_l
+// the public inputs
+#[derive(Clone, Debug, PartialEq)]
+pub struct R1CS {
+    pub m: usize,
+    pub l: usize,
+    pub a: Vec<Row>,
+    pub b: Vec<Row>,
+    pub c: Vec<Row>,
+}
+
+impl R1CS {
+
+    pub fn create(a: Vec<Row>, b: Vec<Row>, c: Vec<Row>, m: usize, l: usize)
+        -> Result<Self, SnarkyError>
+    {
+        if b.len() != a.len() || c.len() != a.len() {
+            return Err(ShapeError::UnequalLengths.into())
+        }
+        if l + 1 > m {
+            return Err(ShapeError::InputsOutOfRange.into())
+        }
+        for row in a.iter().chain(b.iter()).chain(c.iter()) {
+            if row.iter().any(|&(i, _)| i > m) {
+                return Err(ShapeError::IndexOutOfRange.into())
+            }
+        }
+        Ok(Self { m, l, a, b, c })
+    }
+
+    pub fn nr_constraints(&self) -> usize {
+        self.a.len()
+    }
+
+    fn inner_product(row: &Row, assignment: &[F]) -> F {
+        row.iter().fold(F::zero(), |acc, &(i, coeff)| acc + coeff * assignment[i])
+    }
+
+    pub fn is_satisfied(&self, assignment: &[F]) -> bool {
+        if assignment.len() != self.m + 1 {
+            return false;
+        }
+        (0..self.nr_constraints()).all(|k| {
+            Self::inner_product(&self.a[k], assignment) *
+            Self::inner_product(&self.b[k], assignment) ==
+            Self::inner_product(&self.c[k], assignment)
+        })
+    }
+
+    // Interpolates the columns of A, B, C over an evaluation domain with
+    // t the vanishing polynomial of the domain. The constraints z_i * 0 = 0
+    // are appended for the public variables, so that the respective
+    // polynomials are linearly independent.
+    pub fn to_qap(&self) -> Result<ConstraintSystem, SnarkyError> {
+        let (m, l) = (self.m, self.l);
+        let nr_rows = self.nr_constraints() + l + 1;
+        let size = match Univariate::<F>::domain_size(nr_rows) {
+            Some(size) => size,
+            None => return Err(ShapeError::DomainTooLarge.into()),
+        };
+
+        let mut evals_u = vec![vec![F::zero(); nr_rows]; m + 1];
+        let mut evals_v = vec![vec![F::zero(); nr_rows]; m + 1];
+        let mut evals_w = vec![vec![F::zero(); nr_rows]; m + 1];
+        for (matrix, evals) in [
+            (&self.a, &mut evals_u),
+            (&self.b, &mut evals_v),
+            (&self.c, &mut evals_w),
+        ].iter_mut() {
+            for (k, row) in matrix.iter().enumerate() {
+                for &(i, coeff) in row.iter() {
+                    evals[i][k] += coeff;
+                }
+            }
+        }
+        for i in 0..l + 1 {
+            evals_u[i][self.nr_constraints() + i] = F::one();
+        }
+
+        let interpolate = |evals: Vec<Vec<F>>| evals
+            .iter()
+            .map(|e| Univariate::interpolate(e, size).ok_or(ShapeError::DomainTooLarge))
+            .collect::<Result<Vec<_>, _>>();
+        let u = interpolate(evals_u)?;
+        let v = interpolate(evals_v)?;
+        let w = interpolate(evals_w)?;
+        let t = Univariate::vanishing(size).ok_or(ShapeError::DomainTooLarge)?;
+
+        ConstraintSystem::create(u, v, w, t, l)
+    }
+}
@@ -0,0 +1,103 @@
+use backend::{scalar, Scalar};
+use circuits::{R1CS, Row};
+use circuits::arkworks;
+use util::{SnarkyError, CircuitError};
+use ark_relations::lc;
+use ark_relations::r1cs::{
+    ConstraintSynthesizer,
+    ConstraintSystemRef,
+    SynthesisError,
+    Variable,
+};
+
+// x^3 + x + 5 = out
+struct Cubic {
+    x: Option<Scalar>,
+}
+
+impl ConstraintSynthesizer<Scalar> for Cubic {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Scalar>)
+        -> Result<(), SynthesisError>
+    {
+        let x_val = self.x;
+        let x2_val = x_val.map(|x| x * x);
+        let x3_val = x2_val.map(|x2| x2 * x_val.unwrap());
+        let sum_val = x3_val.map(|x3| x3 + x_val.unwrap());
+        let out_val = sum_val.map(|sum| sum + scalar!(5u64));
+
+        let out = cs.new_input_variable(|| out_val.ok_or(SynthesisError::AssignmentMissing))?;
+        let x = cs.new_witness_variable(|| x_val.ok_or(SynthesisError::AssignmentMissing))?;
+        let x2 = cs.new_witness_variable(|| x2_val.ok_or(SynthesisError::AssignmentMissing))?;
+        let x3 = cs.new_witness_variable(|| x3_val.ok_or(SynthesisError::AssignmentMissing))?;
+        let sum = cs.new_witness_variable(|| sum_val.ok_or(SynthesisError::AssignmentMissing))?;
+
+        cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + x2)?;
+        cs.enforce_constraint(lc!() + x2, lc!() + x, lc!() + x3)?;
+        cs.enforce_constraint(lc!() + x3 + x, lc!() + Variable::One, lc!() + sum)?;
+        cs.enforce_constraint(
+            lc!() + sum + (scalar!(5u64), Variable::One),
+            lc!() + Variable::One,
+            lc!() + out,
+        )?;
+        Ok(())
+    }
+}
+
+struct Failing;
+
+impl ConstraintSynthesizer<Scalar> for Failing {
+    fn generate_constraints(self, _: ConstraintSystemRef<Scalar>)
+        -> Result<(), SynthesisError>
+    {
+        Err(SynthesisError::Unsatisfiable)
+    }
+}
+
+// Same circuit over the variables [1, out, x, x^2, x^3, x^3 + x]
+fn expected() -> R1CS {
+    let one = scalar!(1u64);
+    let a: Vec<Row> = vec![
+        vec![(2, one)],
+        vec![(3, one)],
+        vec![(4, one), (2, one)],
+        vec![(5, one), (0, scalar!(5u64))],
+    ];
+    let b: Vec<Row> = vec![
+        vec![(2, one)],
+        vec![(2, one)],
+        vec![(0, one)],
+        vec![(0, one)],
+    ];
+    let c: Vec<Row> = vec![
+        vec![(3, one)],
+        vec![(4, one)],
+        vec![(5, one)],
+        vec![(1, one)],
+    ];
+    R1CS::create(a, b, c, 5, 1).unwrap()
+}
+
+#[test]
+fn test_synthesize_r1cs() {
+    let r1cs = arkworks::synthesize_r1cs(Cubic { x: None }).unwrap();
+    assert_eq!((r1cs.m, r1cs.l), (5, 1));
+    assert_eq!(r1cs.nr_constraints(), 4);
+    let z = [1u64, 35, 3, 9, 27, 30]
+        .iter()
+        .map(|&v| scalar!(v))
+        .collect::<Vec<Scalar>>();
+    assert!(r1cs.is_satisfied(&z));
+}
+
+#[test]
+fn test_synthesize() {
+    let qap = arkworks::synthesize(Cubic { x: Some(scalar!(3u64)) }).unwrap();
+    assert_eq!(qap.shape(), (5, 8, 1));
+    assert_eq!(qap, expected().to_qap().unwrap());
+}
+
+#[test]
+fn test_synthesis_failure() {
+    let err = arkworks::synthesize(Failing).unwrap_err();
+    assert!(matches!(err, SnarkyError::Circuit(CircuitError::Synthesis(_))));
+}
@@ -0,0 +1,196 @@
+use backend::{scalar, Scalar};
+use circuits::{R1CS, Row};
+use circuits::circom::{self, CircomHeader};
+use util::{SnarkyError, CircuitError};
+use ark_ff::{BigInteger, FpParameters, PrimeField};
+use std::io::Cursor;
+
+// x^3 + x + 5 = out, with wires [1, out, x, x^2, x^3, x^3 + x]
+macro_rules! cubic {
+    () => {
+        {
+            let one = scalar!(1u64);
+            let a: Vec<Row> = vec![
+                vec![(2, one)],
+                vec![(3, one)],
+                vec![(4, one), (2, one)],
+                vec![(5, one), (0, scalar!(5u64))],
+            ];
+            let b: Vec<Row> = vec![
+                vec![(2, one)],
+                vec![(2, one)],
+                vec![(0, one)],
+                vec![(0, one)],
+            ];
+            let c: Vec<Row> = vec![
+                vec![(3, one)],
+                vec![(4, one)],
+                vec![(5, one)],
+                vec![(1, one)],
+            ];
+            R1CS::create(a, b, c, 5, 1).unwrap()
+        }
+    }
+}
+
+fn modulus() -> Vec<u8> {
+    <Scalar as PrimeField>::Params::MODULUS.to_bytes_le()
+}
+
+fn encode_row(row: &Row) -> Vec<u8> {
+    let mut bytes = (row.len() as u32).to_le_bytes().to_vec();
+    for &(wire, coeff) in row.iter() {
+        bytes.extend_from_slice(&(wire as u32).to_le_bytes());
+        bytes.extend_from_slice(&coeff.into_repr().to_bytes_le());
+    }
+    bytes
+}
+
+fn section(kind: u32, data: &[u8]) -> Vec<u8> {
+    let mut bytes = kind.to_le_bytes().to_vec();
+    bytes.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(data);
+    bytes
+}
+
+// Encodes with the constraints section preceding the header
+fn encode(r1cs: &R1CS, prime: &[u8], nr_pub_out: u32) -> Vec<u8> {
+    let mut header = (prime.len() as u32).to_le_bytes().to_vec();
+    header.extend_from_slice(prime);
+    for value in [
+        r1cs.m as u32 + 1,
+        nr_pub_out,
+        r1cs.l as u32 - nr_pub_out,
+        (r1cs.m - r1cs.l) as u32,
+    ].iter() {
+        header.extend_from_slice(&value.to_le_bytes());
+    }
+    header.extend_from_slice(&(r1cs.m as u64 + 1).to_le_bytes());
+    header.extend_from_slice(&(r1cs.nr_constraints() as u32).to_le_bytes());
+
+    let mut constraints = Vec::<u8>::new();
+    for k in 0..r1cs.nr_constraints() {
+        for matrix in [&r1cs.a, &r1cs.b, &r1cs.c].iter() {
+            constraints.extend_from_slice(&encode_row(&matrix[k]));
+        }
+    }
+    let labels = (0..r1cs.m as u64 + 1)
+        .flat_map(|i| i.to_le_bytes().to_vec())
+        .collect::<Vec<u8>>();
+
+    let mut bytes = b"r1cs".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&3u32.to_le_bytes());
+    bytes.extend_from_slice(&section(circom::SECTION_CONSTRAINTS, &constraints));
+    bytes.extend_from_slice(&section(circom::SECTION_HEADER, &header));
+    bytes.extend_from_slice(&section(circom::SECTION_WIRE_TO_LABEL, &labels));
+    bytes
+}
+
+#[test]
+fn test_read_header() {
+    let bytes = encode(&cubic!(), &modulus(), 1);
+    let header = circom::read_header(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(header, CircomHeader {
+        nr_wires: 6,
+        nr_pub_out: 1,
+        nr_pub_in: 0,
+        nr_prv_in: 4,
+        nr_labels: 6,
+        nr_constraints: 4,
+    });
+    assert_eq!(header.shape(), (5, 1));
+
+    let crafted = CircomHeader {
+        nr_wires: 0,
+        nr_pub_out: u32::MAX,
+        nr_pub_in: u32::MAX,
+        nr_prv_in: 0,
+        nr_labels: 0,
+        nr_constraints: 0,
+    };
+    assert_eq!(crafted.shape(), (0, 2 * u32::MAX as usize));
+}
+
+#[test]
+fn test_read_r1cs() {
+    let r1cs = cubic!();
+    let bytes = encode(&r1cs, &modulus(), 1);
+    assert_eq!(circom::read_r1cs(&mut Cursor::new(bytes)).unwrap(), r1cs);
+}
+
+#[test]
+fn test_load() {
+    let r1cs = cubic!();
+    let bytes = encode(&r1cs, &modulus(), 1);
+    let qap = circom::load(&mut Cursor::new(bytes)).unwrap();
+    assert_eq!(qap, r1cs.to_qap().unwrap());
+    assert_eq!(qap.shape(), (5, 8, 1));
+}
+
+#[test]
+fn test_prime_mismatch() {
+    // Scalar field of the curve not in use
+    let primes = [
+        [0xffffffff00000001u64, 0x53bda402fffe5bfe, 0x3339d80809a1d805, 0x73eda753299d7d48],
+        [0x43e1f593f0000001u64, 0x2833e84879b97091, 0xb85045b68181585d, 0x30644e72e131a029],
+    ];
+    let other = primes
+        .iter()
+        .map(|limbs| limbs.iter().flat_map(|limb| limb.to_le_bytes().to_vec()).collect::<Vec<u8>>())
+        .find(|prime| *prime != modulus())
+        .unwrap();
+    let bytes = encode(&cubic!(), &other, 1);
+    let err = circom::read_r1cs(&mut Cursor::new(bytes)).unwrap_err();
+    assert_eq!(err, SnarkyError::Circuit(CircuitError::FieldMismatch));
+}
+
+#[test]
+fn test_oversized_field() {
+    // Field size claiming 4 GiB is rejected before reading the prime
+    let mut bytes = encode(&cubic!(), &modulus(), 1);
+    let size = 32u32.to_le_bytes();
+    let pos = bytes
+        .windows(4 + 32)
+        .position(|w| w[..4] == size[..] && w[4..] == modulus()[..])
+        .unwrap();
+    bytes[pos..pos + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = circom::read_r1cs(&mut Cursor::new(bytes)).unwrap_err();
+    assert_eq!(err, SnarkyError::Circuit(CircuitError::FieldMismatch));
+}
+
+#[test]
+fn test_invalid_format() {
+    let r1cs = cubic!();
+    let bytes = encode(&r1cs, &modulus(), 1);
+
+    let mut invalid = bytes.clone();
+    invalid[0] = b'x';
+    assert!(matches!(circom::read_r1cs(&mut Cursor::new(invalid)).unwrap_err(), SnarkyError::Circuit(CircuitError::InvalidFormat(_))));
+
+    let truncated = bytes[..bytes.len() - 20].to_vec();
+    assert!(matches!(circom::read_r1cs(&mut Cursor::new(truncated)).unwrap_err(), SnarkyError::Circuit(CircuitError::InvalidFormat(_))));
+
+    // Section size exceeding i64::MAX must not wrap into a backward seek
+    let mut oversized = bytes.clone();
+    oversized[16..24].copy_from_slice(&(1u64 << 63).to_le_bytes());
+    assert_eq!(
+        circom::read_r1cs(&mut Cursor::new(oversized)).unwrap_err(),
+        SnarkyError::Circuit(CircuitError::InvalidFormat("Detected oversized section"))
+    );
+
+    let mut r1cs_out_of_range = r1cs.clone();
+    r1cs_out_of_range.a[0][0].0 = 6;
+    let invalid = encode(&r1cs_out_of_range, &modulus(), 1);
+    assert!(matches!(circom::read_r1cs(&mut Cursor::new(invalid)).unwrap_err(), SnarkyError::Circuit(CircuitError::InvalidFormat(_))));
+
+    // Unreduced coefficient: replace the 5 of the last constraint by the modulus
+    let mut invalid = bytes;
+    let five = scalar!(5u64).into_repr().to_bytes_le();
+    let pos = invalid
+        .windows(32)
+        .position(|w| w == &five[..])
+        .unwrap();
+    invalid[pos..pos + 32].copy_from_slice(&modulus());
+    assert!(matches!(circom::read_r1cs(&mut Cursor::new(invalid)).unwrap_err(), SnarkyError::Circuit(CircuitError::InvalidFormat(_))));
+}
@@ -1,5 +1,6 @@
 use polynomials::Univariate;
 use circuits::ConstraintSystem;
+use util::{SnarkyError, ShapeError};
 use std::iter::FromIterator;
 
 macro_rules! create_polynomial_collections {
@@ -53,32 +54,32 @@ fn test_QAP_creation() {
 }
 
 #[test]
-fn test_SnarkyError_with_code_101() {
+fn test_SnarkyError_with_unequal_lengths() {
     let (m, n, l) = (5, 4, 3);
     let (mut u, v, w, t) = create_polynomial_collections!(m, n, l, false);
     u.pop();
     let result = ConstraintSystem::create(u, v, w, t, l);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err().code, 101);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::UnequalLengths));
 }
 
 #[test]
-fn test_SnarkyError_with_code_102() {
+fn test_SnarkyError_with_inputs_out_of_range() {
     let (m, n, l) = (3, 4, 3);
     let (u, v, w, t) = create_polynomial_collections!(m, n, l, false);
     let result = ConstraintSystem::create(u, v, w, t, l);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err().code, 102);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::InputsOutOfRange));
 }
 
 #[test]
-fn test_SnarkyError_with_code_103() {
+fn test_SnarkyError_with_degree_mismatch() {
     let (m, n, l) = (5, 4, 3);
     let (mut u, v, w, t) = create_polynomial_collections!(m, n, l, false);
     u[0] = Univariate::create_from_u64(&vec![0; n + 1]);
     let result = ConstraintSystem::create(u, v, w, t, l);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err().code, 103);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::DegreeMismatch));
 }
 
 #[test]
@@ -96,5 +97,24 @@ fn test_SnarkyError_upon_default_creation() {
     let (m, n, l) = (3, 4, 3);
     let result = ConstraintSystem::create_default(m, n, l);
     assert!(result.is_err());
-    assert_eq!(result.unwrap_err().code, 102);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::InputsOutOfRange));
+}
+
+#[test]
+fn test_error_upon_empty_circuit() {
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let t = Univariate::create_from_u64(&vec![1]);
+    let result = ConstraintSystem::create(qap.u, qap.v, qap.w, t, 3);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::EmptyCircuit));
+    let t = Univariate::create_from_u64(&vec![1, 1]);
+    let result = ConstraintSystem::create(vec![], vec![], vec![], t, 0);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::EmptyCircuit));
+    let result = ConstraintSystem::create_default(5, 0, 3);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::EmptyCircuit));
+}
+
+#[test]
+fn test_error_upon_single_constraint() {
+    let result = ConstraintSystem::create_default(5, 1, 3);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::TooFewConstraints));
 }
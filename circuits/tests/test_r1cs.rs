@@ -0,0 +1,107 @@
+use backend::{scalar, Scalar};
+use circuits::{R1CS, Row};
+use util::{SnarkyError, ShapeError};
+use polynomials::Univariate;
+
+// x^3 + x + 5 = out, with variables [1, out, x, x^2, x^3, x^3 + x]
+macro_rules! cubic {
+    () => {
+        {
+            let one = scalar!(1u64);
+            let a: Vec<Row> = vec![
+                vec![(2, one)],
+                vec![(3, one)],
+                vec![(4, one), (2, one)],
+                vec![(5, one), (0, scalar!(5u64))],
+            ];
+            let b: Vec<Row> = vec![
+                vec![(2, one)],
+                vec![(2, one)],
+                vec![(0, one)],
+                vec![(0, one)],
+            ];
+            let c: Vec<Row> = vec![
+                vec![(3, one)],
+                vec![(4, one)],
+                vec![(5, one)],
+                vec![(1, one)],
+            ];
+            R1CS::create(a, b, c, 5, 1).unwrap()
+        }
+    }
+}
+
+fn assignment(x: u64) -> Vec<Scalar> {
+    [1, x * x * x + x + 5, x, x * x, x * x * x, x * x * x + x]
+        .iter()
+        .map(|&v| scalar!(v))
+        .collect()
+}
+
+fn combine(polys: &[Univariate<Scalar>], z: &[Scalar], elm: &Scalar) -> Scalar {
+    polys
+        .iter()
+        .zip(z.iter())
+        .fold(scalar!(0u64), |acc, (p, &z_i)| acc + z_i * p.evaluate(elm))
+}
+
+#[test]
+fn test_r1cs_satisfaction() {
+    let r1cs = cubic!();
+    assert_eq!(r1cs.nr_constraints(), 4);
+    assert!(r1cs.is_satisfied(&assignment(3)));
+    let mut z = assignment(3);
+    z[1] = scalar!(36u64);
+    assert!(!r1cs.is_satisfied(&z));
+    assert!(!r1cs.is_satisfied(&z[..5]));
+}
+
+#[test]
+fn test_r1cs_to_qap() {
+    let r1cs = cubic!();
+    let qap = r1cs.to_qap().unwrap();
+    let (m, n, l) = qap.shape();
+    assert_eq!((m, n, l), (5, 8, 1));     // 4 constraints + 2 public variables
+
+    let (u, v, w, t) = qap.collections();
+    let z = assignment(3);
+    for k in 0..n {
+        let elm = Univariate::<Scalar>::domain_element(n, k).unwrap();
+        assert_eq!(t.evaluate(&elm), scalar!(0u64));
+        assert_eq!(
+            combine(u, &z, &elm) * combine(v, &z, &elm),
+            combine(w, &z, &elm)
+        );
+    }
+
+    let mut z = assignment(3);
+    z[1] = scalar!(36u64);
+    let elm = Univariate::<Scalar>::domain_element(n, 3).unwrap();
+    assert_ne!(
+        combine(u, &z, &elm) * combine(v, &z, &elm),
+        combine(w, &z, &elm)
+    );
+}
+
+#[test]
+fn test_error_upon_unequal_rows() {
+    let mut r1cs = cubic!();
+    r1cs.b.pop();
+    let result = R1CS::create(r1cs.a, r1cs.b, r1cs.c, 5, 1);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::UnequalLengths));
+}
+
+#[test]
+fn test_error_upon_index_exceeding_m() {
+    let mut r1cs = cubic!();
+    r1cs.a[0].push((6, scalar!(1u64)));
+    let result = R1CS::create(r1cs.a, r1cs.b, r1cs.c, 5, 1);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::IndexOutOfRange));
+}
+
+#[test]
+fn test_error_upon_invalid_l() {
+    let r1cs = cubic!();
+    let result = R1CS::create(r1cs.a, r1cs.b, r1cs.c, 5, 5);
+    assert_eq!(result.unwrap_err(), SnarkyError::Shape(ShapeError::InputsOutOfRange));
+}
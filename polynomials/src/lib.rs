@@ -1,4 +1,5 @@
 use std::ops::Add;
+use std::ops::Sub;
 use std::ops::Mul;
 use std::ops::AddAssign;
 use std::ops::MulAssign;
@@ -10,7 +11,8 @@ use ark_ff;
 use ark_poly;
 use ark_poly::Polynomial;
 use ark_poly::UVPolynomial;
-use ark_poly::univariate::DensePolynomial;
+use ark_poly::univariate::{DensePolynomial, DenseOrSparsePolynomial};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
 
 
 #[derive(PartialEq, Debug)]
@@ -58,6 +60,49 @@ impl<F: ark_ff::Field> Univariate<F> {
     pub fn evaluate(&self, elm: &F) -> F {
         self._poly.evaluate(&elm)
     }
+
+    // Degree is that of the wrapped polynomial (-1 for the zero polynomial)
+    fn from_poly(poly: DensePolynomial<F>) -> Self {
+        Self::create(poly.coeffs)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self._poly.coeffs.iter().all(|c| c.is_zero())
+    }
+
+    // Quotient and remainder of euclidean division; None if divisor is zero
+    pub fn divide(&self, divisor: &Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+        let (q, r) = DenseOrSparsePolynomial::from(&self._poly)
+            .divide_with_q_and_r(&DenseOrSparsePolynomial::from(&divisor._poly))?;
+        Some((Self::from_poly(q), Self::from_poly(r)))
+    }
+}
+
+impl<'a, F: ark_ff::Field> Add<&'a Univariate<F>> for &Univariate<F> {
+    type Output = Univariate<F>;
+
+    fn add(self, other: &'a Univariate<F>) -> Univariate<F> {
+        Univariate::from_poly(&self._poly + &other._poly)
+    }
+}
+
+impl<'a, F: ark_ff::Field> Sub<&'a Univariate<F>> for &Univariate<F> {
+    type Output = Univariate<F>;
+
+    fn sub(self, other: &'a Univariate<F>) -> Univariate<F> {
+        Univariate::from_poly(&self._poly - &other._poly)
+    }
+}
+
+impl<'a, F: ark_ff::FftField> Mul<&'a Univariate<F>> for &Univariate<F> {
+    type Output = Univariate<F>;
+
+    fn mul(self, other: &'a Univariate<F>) -> Univariate<F> {
+        Univariate::from_poly(&self._poly * &other._poly)
+    }
 }
 
 impl<F: ark_ff::Field + From<u64>> Univariate<F> {
@@ -70,3 +115,44 @@ impl<F: ark_ff::Field + From<u64>> Univariate<F> {
         Self::create(coeffs)
     }
 }
+
+impl<F: ark_ff::FftField> Univariate<F> {
+
+    // Size of the smallest evaluation domain containing at least n points
+    pub fn domain_size(n: usize) -> Option<usize> {
+        Radix2EvaluationDomain::<F>::new(n).map(|domain| domain.size())
+    }
+
+    // i-th element of the evaluation domain of the given size
+    pub fn domain_element(size: usize, i: usize) -> Option<F> {
+        match Self::domain_size(size) {
+            Some(s) if s == size => {
+                Radix2EvaluationDomain::<F>::new(size).map(|domain| domain.element(i))
+            },
+            _ => None,
+        }
+    }
+
+    // Polynomial of degree < size assuming the provided values over the
+    // evaluation domain of the given size (missing values are taken zero)
+    pub fn interpolate(evals: &[F], size: usize) -> Option<Self> {
+        if evals.len() > size || Self::domain_size(size) != Some(size) {
+            return None;
+        }
+        let domain = Radix2EvaluationDomain::<F>::new(size)?;
+        let mut evals = evals.to_vec();
+        evals.resize(size, F::zero());
+        Some(Self::create(domain.ifft(&evals)))
+    }
+
+    // Polynomial vanishing over the evaluation domain of the given size
+    pub fn vanishing(size: usize) -> Option<Self> {
+        if Self::domain_size(size) != Some(size) {
+            return None;
+        }
+        let mut coeffs = vec![F::zero(); size + 1];
+        coeffs[0] = -F::one();
+        coeffs[size] = F::one();
+        Some(Self::create(coeffs))                  // X^size - 1
+    }
+}
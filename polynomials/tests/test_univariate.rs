@@ -135,3 +135,82 @@ fn test_evaluate() {
         assert_eq!(poly.evaluate(&scalar!(elm)), scalar!(value));
     }
 }
+
+#[test]
+fn test_domain_size() {
+    let parametrization = map! {
+        1 => 1,
+        2 => 2,
+        3 => 4,
+        5 => 8,
+        8 => 8,
+        9 => 16
+    };
+    for (n, expected) in parametrization {
+        assert_eq!(Univariate::<Scalar>::domain_size(n), Some(expected));
+    }
+}
+
+#[test]
+fn test_interpolate() {
+    let size = 8;
+    let evals = (0..5_u64).map(|v| scalar!(v * v + 1)).collect::<Vec<_>>();
+    let poly = Univariate::<Scalar>::interpolate(&evals, size).unwrap();
+    assert_eq!(poly.degree(), size as isize - 1);
+    for i in 0..size {
+        let elm = Univariate::<Scalar>::domain_element(size, i).unwrap();
+        let expected = if i < evals.len() { evals[i] } else { scalar!(0_u64) };
+        assert_eq!(poly.evaluate(&elm), expected);
+    }
+    assert!(Univariate::<Scalar>::interpolate(&evals, 4).is_none());
+    assert!(Univariate::<Scalar>::interpolate(&evals, 6).is_none());
+}
+
+#[test]
+fn test_vanishing() {
+    let size = 8;
+    let t = Univariate::<Scalar>::vanishing(size).unwrap();
+    assert_eq!(t.degree(), size as isize);
+    for i in 0..size {
+        let elm = Univariate::<Scalar>::domain_element(size, i).unwrap();
+        assert_eq!(t.evaluate(&elm), scalar!(0_u64));
+    }
+    assert_ne!(t.evaluate(&scalar!(2_u64)), scalar!(0_u64));
+    assert!(Univariate::<Scalar>::vanishing(6).is_none());
+}
+
+#[test]
+fn test_arithmetic() {
+    let p = Univariate::<Scalar>::create_from_u64(&vec![1, 2, 3]);
+    let q = Univariate::<Scalar>::create_from_u64(&vec![4, 5]);
+    let elm = scalar!(7_u64);
+
+    let sum = &p + &q;
+    assert_eq!(sum.degree(), 2);
+    assert_eq!(sum.evaluate(&elm), p.evaluate(&elm) + q.evaluate(&elm));
+
+    let diff = &p - &p;
+    assert!(diff.is_zero());
+    assert_eq!(diff.degree(), -1);
+
+    let prod = &p * &q;
+    assert_eq!(prod.degree(), 3);
+    assert_eq!(prod.evaluate(&elm), p.evaluate(&elm) * q.evaluate(&elm));
+}
+
+#[test]
+fn test_divide() {
+    let p = Univariate::<Scalar>::create_from_u64(&vec![1, 2, 3]);
+    let q = Univariate::<Scalar>::create_from_u64(&vec![4, 5]);
+    let r = Univariate::<Scalar>::create_from_u64(&vec![6]);
+
+    let (quot, rem) = (&(&p * &q) + &r).divide(&q).unwrap();
+    assert_eq!(quot, p);
+    assert_eq!(rem, r);
+
+    let (quot, rem) = (&p * &q).divide(&p).unwrap();
+    assert_eq!(quot, q);
+    assert!(rem.is_zero());
+
+    assert!(p.divide(&Univariate::create(vec![])).is_none());
+}
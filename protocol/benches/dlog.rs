@@ -27,7 +27,7 @@ fn bench_prove_dlog(c: &mut Criterion) {
     let witness = scalar!(100_u64);
     c.bench_function(
         "scalar!",
-        |b| b.iter(|| Dlog::prove(&commit, witness))
+        |b| b.iter(|| Dlog::prove(&commit, &witness))
     );
 }
 
@@ -37,7 +37,7 @@ fn bench_verify_dlog(c: &mut Criterion) {
     let elm2 = smul2!(scalar!(100_u64), genG2!());
     let commit = (elm1, elm2);
     let witness = scalar!(100_u64);
-    let proof = Dlog::prove(&commit, witness);
+    let proof = Dlog::prove(&commit, &witness).unwrap();
     c.bench_function(
         "scalar!",
         |b| b.iter(|| Dlog::verify(ctx, &commit, &proof))
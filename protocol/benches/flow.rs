@@ -1,4 +1,4 @@
-use protocol::{SRS, Trapdoor, BatchProof, Phase, Verification};
+use protocol::{SRS, UniversalSRS, Trapdoor, BatchProof, Verification};
 use circuits::ConstraintSystem;
 use criterion::{
     black_box, 
@@ -9,6 +9,7 @@ use criterion::{
 };
 
 fn bench_setup(c: &mut Criterion) {
+    let mut rng = ::util::snarky_rng();
     let mut group = c.benchmark_group("setup");
     for (m, n, l) in [
         (30, 20, 10),
@@ -18,31 +19,32 @@ fn bench_setup(c: &mut Criterion) {
         let qap = ConstraintSystem::create_default(*m, *n, *l).unwrap();
         group.bench_function(
             format!("create SRS with m:{}, n:{}, l:{}", m, n, l),
-            |b| b.iter(|| SRS::setup_with_random_trapdoor(&qap)),
+            |b| b.iter(|| SRS::setup_with_random_trapdoor(&qap, &mut rng)),
         );
     }
     group.finish();
 }
 
 fn bench_update_phase_1(c: &mut Criterion) {
+    let mut rng = ::util::snarky_rng();
     let mut group = c.benchmark_group("update");
     for (m, n, l) in [
         (30, 20, 10),
         (300, 200, 100),
         (3000, 2000, 1000),
     ].iter() {
-        let qap = ConstraintSystem::create_default(*m, *n, *l).unwrap();
-        let (mut srs, trp) = SRS::setup_with_random_trapdoor(&qap);
+        let (mut usrs, trp) = UniversalSRS::setup_with_random_trapdoor(*n, &mut rng).unwrap();
         let mut batch = BatchProof::initiate();
         group.bench_function(
             format!("Phase 1 SRS update with m:{}, n:{}, l:{}", m, n, l),
-            |b| b.iter(|| protocol::update(&qap, &mut srs, &mut batch, Phase::ONE)),
+            |b| b.iter(|| protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap()),
         );
     }
     group.finish();
 }
 
 fn bench_update_phase_2(c: &mut Criterion) {
+    let mut rng = ::util::snarky_rng();
     let mut group = c.benchmark_group("update");
     for (m, n, l) in [
         (30, 20, 10),
@@ -50,17 +52,18 @@ fn bench_update_phase_2(c: &mut Criterion) {
         (3000, 2000, 1000),
     ].iter() {
         let qap = ConstraintSystem::create_default(*m, *n, *l).unwrap();
-        let (mut srs, trp) = SRS::setup_with_random_trapdoor(&qap);
+        let (mut srs, trp) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
         let mut batch = BatchProof::initiate();
         group.bench_function(
             format!("Phase 2 SRS update with m:{}, n:{}, l:{}", m, n, l),
-            |b| b.iter(|| protocol::update(&qap, &mut srs, &mut batch, Phase::TWO)),
+            |b| b.iter(|| protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap()),
         );
     }
     group.finish();
 }
 
 fn bench_verify(c: &mut Criterion) {
+    let mut rng = ::util::snarky_rng();
     let mut group = c.benchmark_group("verify");
     // TODO: Parametrize phases
     for (m, n, l) in [
@@ -69,10 +72,11 @@ fn bench_verify(c: &mut Criterion) {
         (3000, 2000, 1000),
     ].iter() {
         let qap = ConstraintSystem::create_default(*m, *n, *l).unwrap();
-        let (mut srs, trp) = SRS::setup_with_random_trapdoor(&qap);
+        let (mut usrs, trp) = UniversalSRS::setup_with_random_trapdoor(*n, &mut rng).unwrap();
         let mut batch = BatchProof::initiate();
-        protocol::update(&qap, &mut srs, &mut batch, Phase::ONE);
-        protocol::update(&qap, &mut srs, &mut batch, Phase::TWO);
+        protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+        let mut srs = usrs.specialize(&qap).unwrap();
+        protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
         group.bench_function(
             format!("Verify SRS with m:{}, n:{}, l:{}", m, n, l),
             |b| b.iter(|| protocol::verify_naive(&qap, &srs, &batch)),
@@ -82,6 +86,7 @@ fn bench_verify(c: &mut Criterion) {
 }
 
 fn bench_flow(c: &mut Criterion) {
+    let mut rng = ::util::snarky_rng();
     let mut group = c.benchmark_group("flow");
     // TODO: Parametrize phases
     for (m, n, l) in [
@@ -94,9 +99,10 @@ fn bench_flow(c: &mut Criterion) {
             format!("Verify SRS with m:{}, n:{}, l:{}", m, n, l),
             |b| b.iter(|| {
                 let qap = ConstraintSystem::create_default(*m, *n, *l).unwrap();
-                let (mut srs, trp) = SRS::setup_with_random_trapdoor(&qap);
-                protocol::update(&qap, &mut srs, &mut batch, Phase::ONE);
-                protocol::update(&qap, &mut srs, &mut batch, Phase::TWO);
+                let (mut usrs, trp) = UniversalSRS::setup_with_random_trapdoor(*n, &mut rng).unwrap();
+                protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+                let mut srs = usrs.specialize(&qap).unwrap();
+                protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
                 protocol::verify_naive(&qap, &srs, &batch)
             }),
         );
@@ -105,25 +111,27 @@ fn bench_flow(c: &mut Criterion) {
 }
 
 fn bench_verify_comparison(c: &mut Criterion) {
+    let mut rng = ::util::snarky_rng();
     let NR_1 = 500;
     let NR_2 = NR_1;
 
     let mut group = c.benchmark_group("verify");
     let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
-    let (mut srs, trp) = SRS::setup_with_random_trapdoor(&qap);
+    let (mut usrs, trp) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
     let mut batch = BatchProof::initiate();
 
     // phase 1 updates
     let mut count = 0;
     while count < NR_1 {
-        protocol::update(&qap, &mut srs, &mut batch, Phase::ONE);
+        protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
         count += 1;
     }
+    let mut srs = usrs.specialize(&qap).unwrap();
 
     // phase 2 updates
     let mut count = 0;
     while count < NR_2 {
-        protocol::update(&qap, &mut srs, &mut batch, Phase::ONE);
+        protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
         count += 1;
     }
 
@@ -10,7 +10,7 @@ pub fn main() {
     let elm2 = genG2!();
     let commit = (elm1, elm2);
     let start = Instant::now();
-    Dlog::rndoracle(&commit);
+    Dlog::rndoracle(&commit).unwrap();
     println!("[+] Random oracle ({:.2?})", start.elapsed());
 
     let elm1 = smul1!(100_u64, genG1!());
@@ -18,7 +18,7 @@ pub fn main() {
     let commit = (elm1, elm2);
     let witness = scalar!(100_u64);
     let start = Instant::now();
-    let proof = Dlog::prove(&commit, witness);
+    let proof = Dlog::prove(&commit, &witness).unwrap();
     println!("[+] dlog proof ({:.2?})", start.elapsed());
 
     let ctx = (&genG1!(), &genG2!());
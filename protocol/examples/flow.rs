@@ -1,6 +1,6 @@
 use std::time::Instant;
 use circuits::ConstraintSystem;
-use protocol::{SRS, Trapdoor, BatchProof, Phase, Verification};
+use protocol::{UniversalSRS, BatchProof};
 use protocol;
 
 fn main() {
@@ -9,11 +9,13 @@ fn main() {
     let n = util::parse_arg(2, "40", "n should be a positive integer");
     let l = util::parse_arg(3, "30", "l should be a positive integer");
 
-    let nr_1 = util::parse_arg(4, "5", "phase 1 repeats should be a non-negative integer");
-    let nr_2 = util::parse_arg(5, "5", "phase 2 repeats should be a non-negative integer");
+    let nr_1: usize = util::parse_arg(4, "5", "phase 1 repeats should be a non-negative integer");
+    let nr_2: usize = util::parse_arg(5, "5", "phase 2 repeats should be a non-negative integer");
 
     let naive = util::parse_arg::<bool>(6, "false", "Should be truly");
 
+    let mut rng = ::util::snarky_rng();
+
     println!("--------------------------");
     let start = Instant::now();
 
@@ -35,11 +37,11 @@ fn main() {
         }
     };
 
-    let (mut srs, trp) = {
+    let mut usrs = {
         let start = Instant::now();
-        let (srs, trp) = SRS::setup_with_unit_trapdoor(&qap);
-        println!("[+] Initialized SRS ({:.2?})", start.elapsed());
-        (srs, trp)
+        let (usrs, _) = UniversalSRS::setup_with_unit_trapdoor(n).unwrap();
+        println!("[+] Initialized universal SRS ({:.2?})", start.elapsed());
+        usrs
     };
 
     let mut batch = BatchProof::initiate();
@@ -48,7 +50,7 @@ fn main() {
     let mut count = 0;
     loop {
         let start = Instant::now();
-        protocol::update(&qap, &mut srs, &mut batch, Phase::ONE);
+        protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
         println!("[+] Phase 1 SRS update ({:.2?})", start.elapsed());
         count += 1;
         if count == nr_1 {
@@ -56,11 +58,18 @@ fn main() {
         }
     }
 
+    let mut srs = {
+        let start = Instant::now();
+        let srs = usrs.specialize(&qap).unwrap();
+        println!("[+] Specialized SRS ({:.2?})", start.elapsed());
+        srs
+    };
+
     // phase 2 updates
     let mut count = 0;
     loop {
         let start = Instant::now();
-        protocol::update(&qap, &mut srs, &mut batch, Phase::TWO);
+        protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
         println!("[+] Phase 2 SRS update ({:.2?})", start.elapsed());
         count += 1;
         if count == nr_2 {
@@ -72,7 +81,7 @@ fn main() {
         let start = Instant::now();
         let res = match naive {
             true    => protocol::verify_naive(&qap, &srs, &batch),
-            false   => protocol::verify(&qap, &srs, &batch),
+            false   => protocol::verify(&qap, &srs, &batch, &mut rng),
         };
         println!("[+] {:?} ({:.2?})", res, start.elapsed());
         res
@@ -0,0 +1,150 @@
+use crate::prover::{BatchProof, UpdateProof};
+use crate::srs::{SRS, UniversalSRS, Trapdoor};
+use crate::updater::{update, update_universal};
+use crate::verifier::{Verification, verify};
+use circuits::ConstraintSystem;
+use util::{SnarkyRng, SnarkyError, ShapeError, ProofError};
+pub use util::CeremonyError;
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Stage {
+    PhaseOne,
+    PhaseTwo,
+    Finalized,
+}
+
+// Parameters under update: universal during phase 1, specialized to the QAP
+// afterwards
+#[derive(Clone, Debug, PartialEq)]
+enum Params {
+    Universal(UniversalSRS),
+    Specialized(Box<SRS>),
+}
+
+// Owns the QAP, the SRS and the batch of update-proofs, enforcing the order
+// initialize -> phase 1 -> specialize -> phase 2 -> finalize. Operations
+// illegal at the current stage fail with CeremonyError, leaving it untouched.
+#[derive(Debug, PartialEq)]
+pub struct Ceremony {
+    qap: ConstraintSystem,
+    params: Params,
+    batch: BatchProof,
+    finalized: bool,
+}
+
+impl Ceremony {
+
+    // Starts phase 1 from a random trapdoor, which is known to nobody: it is
+    // zeroized as soon as the initial parameters are computed. Fails with
+    // CeremonyError::TooFewConstraints if the QAP has degree less than 2
+    pub fn initialize<R: SnarkyRng>(qap: ConstraintSystem, rng: &mut R)
+        -> Result<Self, SnarkyError>
+    {
+        let n = Self::check_degree(&qap)?;
+        let usrs = UniversalSRS::create(&Trapdoor::random(rng), n)?;
+        Self::resume(qap, usrs, BatchProof::initiate())
+    }
+
+    // Resumes phase 1 from the provided parameters, e.g., imported or
+    // persisted ones along with their update-proofs
+    pub fn resume(qap: ConstraintSystem, usrs: UniversalSRS, batch: BatchProof)
+        -> Result<Self, SnarkyError>
+    {
+        Self::check_degree(&qap)?;
+        usrs.check_shape()?;
+        if !batch.batch_2.is_empty() {
+            return Err(ProofError::PhaseMismatch.into());
+        }
+        let (_, n, _) = qap.shape();
+        if n > usrs.degree() {
+            return Err(ShapeError::SrsMismatch.into());
+        }
+        Ok(Self { qap, params: Params::Universal(usrs), batch, finalized: false })
+    }
+
+    // Degree of the QAP, which must be at least 2 for the SRS to be defined
+    fn check_degree(qap: &ConstraintSystem) -> Result<usize, CeremonyError> {
+        let (_, n, _) = qap.shape();
+        match n < 2 {
+            true    => Err(CeremonyError::TooFewConstraints),
+            _       => Ok(n)
+        }
+    }
+
+    pub fn stage(&self) -> Stage {
+        match (&self.params, self.finalized) {
+            (_, true)                   => Stage::Finalized,
+            (Params::Universal(_), _)   => Stage::PhaseOne,
+            (Params::Specialized(_), _) => Stage::PhaseTwo,
+        }
+    }
+
+    pub fn qap(&self) -> &ConstraintSystem {
+        &self.qap
+    }
+
+    pub fn batch(&self) -> &BatchProof {
+        &self.batch
+    }
+
+    // None before specialization
+    pub fn srs(&self) -> Option<&SRS> {
+        match &self.params {
+            Params::Specialized(srs)    => Some(srs),
+            _                           => None,
+        }
+    }
+
+    // Updates the parameters with respect to the phase of the current stage
+    pub fn contribute<R: SnarkyRng>(&mut self, rng: &mut R)
+        -> Result<UpdateProof, SnarkyError>
+    {
+        if self.finalized {
+            return Err(CeremonyError::Finalized.into());
+        }
+        match &mut self.params {
+            Params::Universal(usrs)  => update_universal(usrs, &mut self.batch, rng),
+            Params::Specialized(srs) => update(&self.qap, srs, &mut self.batch, rng),
+        }
+    }
+
+    // Transition to phase 2
+    pub fn specialize(&mut self) -> Result<(), SnarkyError> {
+        let srs = match (&self.params, self.finalized) {
+            (_, true)                       => return Err(CeremonyError::Finalized.into()),
+            (Params::Specialized(_), _)     => return Err(CeremonyError::AlreadySpecialized.into()),
+            (Params::Universal(usrs), _)    => usrs.specialize(&self.qap)?,
+        };
+        self.params = Params::Specialized(Box::new(srs));
+        Ok(())
+    }
+
+    // Verifies all contributions so far; during phase 1, against the
+    // parameters specialized to the QAP
+    pub fn verify<R: SnarkyRng>(&self, rng: &mut R) -> Verification {
+        match &self.params {
+            Params::Specialized(srs) => verify(&self.qap, srs, &self.batch, rng),
+            Params::Universal(usrs)  => match usrs.specialize(&self.qap) {
+                Ok(srs) => verify(&self.qap, &srs, &self.batch, rng),
+                Err(_)  => Verification::FAILURE,
+            },
+        }
+    }
+
+    // Closes the ceremony, returning the final SRS. Fails with
+    // ProofError::BatchFailure, leaving phase 2 open, if verification fails
+    pub fn finalize<R: SnarkyRng>(&mut self, rng: &mut R) -> Result<&SRS, SnarkyError> {
+        match (&self.params, self.finalized) {
+            (_, true)                       => Err(CeremonyError::Finalized.into()),
+            (Params::Universal(_), _)       => Err(CeremonyError::NotSpecialized.into()),
+            (Params::Specialized(srs), _)   => {
+                if !bool::from(verify(&self.qap, srs, &self.batch, rng)) {
+                    return Err(ProofError::BatchFailure.into());
+                }
+                self.finalized = true;
+                Ok(srs)
+            },
+        }
+    }
+}
@@ -0,0 +1,294 @@
+// Groth16 proving over the SRS produced by the ceremony. With a, b the
+// phase 1 trapdoor scalars (alpha, beta), d the phase 2 one (delta) and
+// z = (1, z_1, ..., z_m) a satisfying assignment, the proof is
+//
+// A = a + U(x) + r d,  B = b + V(x) + s d,
+// C = (sum_{i > l} z_i (b u_i(x) + a v_i(x) + w_i(x)) + h(x) t(x)) / d
+//      + s A + r B - r s d
+//
+// in the respective groups, where U = sum z_i u_i, V = sum z_i v_i,
+// W = sum z_i w_i and h = (U V - W) / t. Since the ceremony has no gamma
+// (i.e., gamma = 1), proofs verify against public inputs z_1, ..., z_l by
+//
+// e(A, B) = e(a, b) e(sum_{i <= l} z_i (b u_i(x) + a v_i(x) + w_i(x)), 1) e(C, d)
+
+use crate::srs::SRS;
+use crate::serialize::*;
+use circuits::ConstraintSystem;
+use util::{SnarkyError, SnarkyRng};
+pub use util::Groth16Error;
+use polynomials::Univariate;
+use backend::*;
+
+use num_traits::identities::{Zero, One};
+use ark_ec::{AffineCurve, PairingEngine};
+use ark_std::rand::Rng as ArkRng;
+
+use rayon::prelude::*;
+use zeroize::Zeroizing;
+
+use std::io::{Read, Write};
+
+type G1 = G1Elem;
+type G2 = G2Elem;
+type Coeffs = Vec<Scalar>;
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Proof {
+    pub a: G1,
+    pub b: G2,
+    pub c: G1,
+}
+
+
+// Coefficients of sum_i z_i p_i, where the p_i have degree < n
+fn combine(polys: &[Univariate<Scalar>], assignment: &[Scalar], n: usize) -> Coeffs {
+    (0..n)
+        .into_par_iter()
+        .map(|j| {
+            polys
+                .iter()
+                .zip(assignment.iter())
+                .fold(zero!(), |acc, (p, &z)| acc + z * p.coeff(j))
+        })
+        .collect()
+}
+
+fn check_shape(qap: &ConstraintSystem, srs: &SRS) -> bool {
+    let (m, n, l) = qap.shape();
+    let (srs_u, srs_s) = (&srs.u, &srs.s);
+    srs_u.0.len() == 2 * n - 1 &&
+    srs_u.1.len() == n &&
+    srs_s.2.len() == m - l &&
+    srs_s.3.len() == n - 1
+}
+
+
+// Coefficients of U, V and h = (U V - W) / t for the provided assignment
+pub fn quotient(qap: &ConstraintSystem, assignment: &[Scalar])
+    -> Result<(Coeffs, Coeffs, Coeffs), Groth16Error>
+{
+    let (m, n, _) = qap.shape();
+    let (u, v, w, t) = qap.collections();
+    if assignment.len() != m + 1 || ct_ne!(assignment[0], one!()) {
+        return Err(Groth16Error::InvalidAssignment);
+    }
+
+    let coeffs_u = combine(u, assignment, n);
+    let coeffs_v = combine(v, assignment, n);
+    let coeffs_w = combine(w, assignment, n);
+    let poly_u = Univariate::create(coeffs_u.clone());
+    let poly_v = Univariate::create(coeffs_v.clone());
+    let poly_w = Univariate::create(coeffs_w);
+
+    let (h, rem) = (&(&poly_u * &poly_v) - &poly_w)
+        .divide(t)
+        .ok_or(Groth16Error::ShapeMismatch)?;
+    if !rem.is_zero() {
+        return Err(Groth16Error::Unsatisfied);
+    }
+    if h.degree() > n as isize - 2 {
+        return Err(Groth16Error::ShapeMismatch);
+    }
+    let coeffs_h = (0..n - 1).map(|j| h.coeff(j)).collect();
+    Ok((coeffs_u, coeffs_v, coeffs_h))
+}
+
+// Proof with the provided zero-knowledge randomizers, passed by reference so
+// that they are not copied beyond the caller's (zeroizing) storage
+pub fn create_proof(
+    qap: &ConstraintSystem,
+    srs: &SRS,
+    assignment: &[Scalar],
+    r: &Scalar,
+    s: &Scalar,
+) -> Result<Proof, Groth16Error> {
+    if !check_shape(qap, srs) {
+        return Err(Groth16Error::ShapeMismatch);
+    }
+    let (coeffs_u, coeffs_v, coeffs_h) = quotient(qap, assignment)?;
+    let (_, n, l) = qap.shape();
+    let (srs_u, srs_s) = (&srs.u, &srs.s);
+
+    let powers_1 = srs_u.0[..n].iter().map(|p| p.0).collect::<Vec<G1>>();
+    let powers_2 = srs_u.0[..n].iter().map(|p| p.1).collect::<Vec<G2>>();
+    let (alpha_1, beta_1, beta_2) = (srs_u.1[0].0, srs_u.1[0].1, srs_u.1[0].3);
+    let (delta_1, delta_2) = (srs_s.0, srs_s.1);
+
+    let rs = Zeroizing::new(*r * s);
+    let a = add1!(
+        alpha_1,
+        msm1!(powers_1, coeffs_u),
+        smul1!(*r, delta_1)
+    );
+    let b = add2!(
+        beta_2,
+        msm2!(powers_2, coeffs_v),
+        smul2!(*s, delta_2)
+    );
+    let b_1 = add1!(
+        beta_1,
+        msm1!(powers_1, coeffs_v),
+        smul1!(*s, delta_1)
+    );
+    let c = add1!(
+        msm1!(srs_s.2, assignment[l + 1..]),
+        msm1!(srs_s.3, coeffs_h),
+        smul1!(*s, a),
+        smul1!(*r, b_1),
+        smul1!(-*rs, delta_1)
+    );
+    Ok(Proof { a, b, c })
+}
+
+pub fn prove<R: SnarkyRng>(qap: &ConstraintSystem, srs: &SRS, assignment: &[Scalar], rng: &mut R)
+    -> Result<Proof, Groth16Error>
+{
+    let r = Zeroizing::new(rscalar!(rng));
+    let s = Zeroizing::new(rscalar!(rng));
+    create_proof(qap, srs, assignment, &r, &s)
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerifyingKey {
+    pub alpha_1: G1,
+    pub beta_2: G2,
+    pub delta_2: G2,
+    pub query: Vec<G1>,         // [b u_i(x) + a v_i(x) + w_i(x)]_1, i <= l
+}
+
+impl VerifyingKey {
+
+    pub fn from_srs(qap: &ConstraintSystem, srs: &SRS) -> Result<Self, Groth16Error> {
+        if !check_shape(qap, srs) {
+            return Err(Groth16Error::ShapeMismatch);
+        }
+        let (_, n, l) = qap.shape();
+        let (u, v, w, _) = qap.collections();
+        let (srs_u, srs_s) = (&srs.u, &srs.s);
+
+        let bases = (0..n)
+            .flat_map(|j| vec![srs_u.1[j].1, srs_u.1[j].0, srs_u.0[j].0])
+            .collect::<Vec<G1>>();
+        let query = (0..l + 1)
+            .into_par_iter()
+            .map(|i| {
+                let scalars = (0..n)
+                    .flat_map(|j| vec![u[i].coeff(j), v[i].coeff(j), w[i].coeff(j)])
+                    .collect::<Vec<Scalar>>();
+                msm1!(bases, scalars)
+            })
+            .collect();
+        let vk = Self {
+            alpha_1: srs_u.1[0].0,
+            beta_2: srs_u.1[0].3,
+            delta_2: srs_s.1,
+            query,
+        };
+        vk.validate(qap)?;
+        Ok(vk)
+    }
+
+    // The query must consist of exactly l + 1 elements
+    pub fn validate(&self, qap: &ConstraintSystem) -> Result<(), Groth16Error> {
+        let (_, _, l) = qap.shape();
+        match self.query.len() == l + 1 {
+            false   => Err(Groth16Error::InvalidVerifyingKey),
+            _       => Ok(())
+        }
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W, encoding: Encoding)
+        -> Result<(), SerializationError>
+    {
+        write_preamble(writer, &VKEY_MAGIC, encoding)?;
+        write_g1(writer, &self.alpha_1, encoding)?;
+        write_g2(writer, &self.beta_2, encoding)?;
+        write_g2(writer, &self.delta_2, encoding)?;
+        write_u64(writer, self.query.len() as u64)?;
+        for elem in self.query.iter() {
+            write_g1(writer, elem, encoding)?;
+        }
+        Ok(())
+    }
+
+    // Fails with InvalidVerifyingKey if the query length does not match the
+    // public inputs of the QAP, before any query element is read
+    pub fn read<R: Read>(reader: &mut R, qap: &ConstraintSystem) -> Result<Self, SnarkyError> {
+        let (_, encoding) = read_preamble(reader, &VKEY_MAGIC)?;
+        let alpha_1 = read_g1(reader, encoding)?;
+        let beta_2 = read_g2(reader, encoding)?;
+        let delta_2 = read_g2(reader, encoding)?;
+        let (_, _, l) = qap.shape();
+        if read_u64(reader)? != l as u64 + 1 {
+            return Err(Groth16Error::InvalidVerifyingKey.into());
+        }
+        let mut query = Vec::with_capacity(l + 1);
+        for _ in 0..l + 1 {
+            query.push(read_g1(reader, encoding)?);
+        }
+        Ok(Self { alpha_1, beta_2, delta_2, query })
+    }
+
+    // None if the query is empty, i.e., the key is malformed
+    pub fn nr_inputs(&self) -> Option<usize> {
+        self.query.len().checked_sub(1)
+    }
+
+    pub fn prepare(&self) -> PreparedVerifyingKey {
+        PreparedVerifyingKey {
+            alpha_beta: pair!(self.alpha_1, self.beta_2),
+            neg_gamma_2: (-genG2!()).into(),
+            neg_delta_2: (-self.delta_2).into(),
+            vk: self.clone(),
+        }
+    }
+}
+
+
+// Verifying key along with precomputed pairing data
+#[derive(Clone, Debug)]
+pub struct PreparedVerifyingKey {
+    pub alpha_beta: GtElem,
+    pub neg_gamma_2: <Engine as PairingEngine>::G2Prepared,
+    pub neg_delta_2: <Engine as PairingEngine>::G2Prepared,
+    pub vk: VerifyingKey,
+}
+
+impl From<VerifyingKey> for PreparedVerifyingKey {
+    fn from(vk: VerifyingKey) -> Self {
+        vk.prepare()
+    }
+}
+
+
+// Public inputs are z_1, ..., z_l (excluding the leading unit)
+pub fn verify_proof(pvk: &PreparedVerifyingKey, public_inputs: &[Scalar], proof: &Proof)
+    -> Result<bool, Groth16Error>
+{
+    let vk = &pvk.vk;
+    match vk.nr_inputs() {
+        None => return Err(Groth16Error::InvalidVerifyingKey),
+        Some(l) if l != public_inputs.len() => return Err(Groth16Error::InvalidPublicInputs),
+        _ => {}
+    }
+    if !(
+        contained_in_group!(proof.a) &
+        contained_in_group!(proof.b) &
+        contained_in_group!(proof.c)
+    ) {
+        return Ok(false);
+    }
+    let acc = add1!(
+        vk.query[0],
+        msm1!(vk.query[1..], public_inputs)
+    );
+    let product = Engine::product_of_pairings(&[
+        (proof.a.into(), proof.b.into()),
+        (acc.into(), pvk.neg_gamma_2.clone()),
+        (proof.c.into(), pvk.neg_delta_2.clone()),
+    ]);
+    Ok(ct_eq!(product, pvk.alpha_beta))
+}
@@ -0,0 +1,130 @@
+//! Importer of the Ethereum KZG ceremony transcript (`transcript.json`).
+//!
+//! The transcript consists of several sub-transcripts, each containing
+//! powers of tau in G1 and G2 in compressed (zcash) encoding as hex strings.
+
+use crate::import::*;
+use crate::prover::BatchProof;
+use crate::srs::UniversalSRS;
+use circuits::ConstraintSystem;
+use util::SnarkyRng;
+
+use serde::Deserialize;
+use std::convert::TryInto;
+use std::io::Read;
+
+
+#[derive(Deserialize)]
+struct Powers {
+    #[serde(rename = "G1Powers")]
+    g1_powers: Vec<String>,
+    #[serde(rename = "G2Powers")]
+    g2_powers: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubTranscript {
+    num_g1_powers: usize,
+    num_g2_powers: usize,
+    powers_of_tau: Powers,
+}
+
+// Parsed transcript; points are decoded lazily upon import
+#[derive(Deserialize)]
+pub struct KzgTranscript {
+    transcripts: Vec<SubTranscript>,
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, ImportError> {
+    let hex = hex.strip_prefix("0x").ok_or(ImportError::InvalidFormat)?;
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return Err(ImportError::InvalidFormat);
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16)
+            .map_err(|_| ImportError::InvalidFormat))
+        .collect()
+}
+
+fn decode_point_1(hex: &str) -> Result<G1Elem, ImportError> {
+    let bytes: [u8; 48] = decode_hex(hex)?
+        .try_into()
+        .map_err(|_| ImportError::InvalidPoint)?;
+    check_point_1(zcash::g1_from_compressed(&bytes).ok_or(ImportError::InvalidPoint)?)
+}
+
+fn decode_point_2(hex: &str) -> Result<G2Elem, ImportError> {
+    let bytes: [u8; 96] = decode_hex(hex)?
+        .try_into()
+        .map_err(|_| ImportError::InvalidPoint)?;
+    check_point_2(zcash::g2_from_compressed(&bytes).ok_or(ImportError::InvalidPoint)?)
+}
+
+impl KzgTranscript {
+
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, ImportError> {
+        let transcript: Self = serde_json::from_reader(reader)
+            .map_err(|_| ImportError::InvalidFormat)?;
+        for sub in transcript.transcripts.iter() {
+            if sub.num_g1_powers != sub.powers_of_tau.g1_powers.len() ||
+                sub.num_g2_powers != sub.powers_of_tau.g2_powers.len() {
+                return Err(ImportError::InvalidFormat);
+            }
+        }
+        Ok(transcript)
+    }
+
+    // Numbers of powers [x^i]_1, [x^i]_2 per sub-transcript
+    pub fn nr_powers(&self) -> Vec<(usize, usize)> {
+        self.transcripts
+            .iter()
+            .map(|sub| (sub.num_g1_powers, sub.num_g2_powers))
+            .collect()
+    }
+
+    // Maximum n supported by the transcript
+    pub fn max_degree(&self) -> usize {
+        self.nr_powers()
+            .iter()
+            .map(|&(nr_1, nr_2)| max_degree(nr_1, nr_2))
+            .max()
+            .unwrap_or(0)
+    }
+
+    // Builds the universal SRS of the QAP degree from the smallest sub-transcript
+    // sufficing for it, along with the (initially empty) batch of phase 1
+    // update-proofs. Fails with InvalidPowers if these are not successive
+    // powers of tau.
+    pub fn import<R: SnarkyRng>(&self, qap: &ConstraintSystem, rng: &mut R)
+        -> Result<(UniversalSRS, BatchProof), ImportError>
+    {
+        let required = required_powers(qap);
+        let available = self.transcripts
+            .iter()
+            .map(|sub| std::cmp::min(sub.num_g1_powers, sub.num_g2_powers))
+            .max()
+            .unwrap_or(0);
+        let sub = self.transcripts
+            .iter()
+            .filter(|sub| std::cmp::min(sub.num_g1_powers, sub.num_g2_powers) >= required)
+            .min_by_key(|sub| sub.num_g1_powers)
+            .ok_or(ImportError::InsufficientPowers { available, required })?;
+        let powers_1 = sub.powers_of_tau.g1_powers[..required]
+            .iter()
+            .map(|hex| decode_point_1(hex))
+            .collect::<Result<Vec<_>, _>>()?;
+        let powers_2 = sub.powers_of_tau.g2_powers[..required]
+            .iter()
+            .map(|hex| decode_point_2(hex))
+            .collect::<Result<Vec<_>, _>>()?;
+        universal_from_powers(qap, &powers_1, &powers_2, rng)
+    }
+}
+
+pub fn import<R: Read, G: SnarkyRng>(reader: &mut R, qap: &ConstraintSystem, rng: &mut G)
+    -> Result<(UniversalSRS, BatchProof), ImportError>
+{
+    KzgTranscript::read(reader)?.import(qap, rng)
+}
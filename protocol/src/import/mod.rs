@@ -0,0 +1,122 @@
+//! Importers of phase 1 parameters produced by public Powers-of-Tau
+//! ceremonies, so that phase 2 can start from a widely trusted phase 1.
+//!
+//! Such ceremonies provide only the powers `[x^i]_1, [x^i]_2`, which are
+//! checked upon import to be successive powers of the same `x`. They are
+//! imported as a `UniversalSRS` of the QAP degree, whose remaining powers
+//! `[a x^i], [b x^i]` are populated with unit `a, b`; these are public until
+//! randomized by a phase 1 update (`update_universal`) applied on top of the
+//! imported parameters. The returned batch of update-proofs is empty, and the
+//! imported parameters are marked as pending an update, so that their
+//! specialization fails with `ImportError::NotUpdated` until at least one
+//! phase 1 update has been applied.
+
+pub mod ptau;
+backend::cfg_bls12_381! {
+    pub mod kzg;         // The Ethereum KZG ceremony is over BLS12-381
+}
+
+use crate::prover::BatchProof;
+use crate::srs::UniversalSRS;
+use circuits::ConstraintSystem;
+use backend::*;
+use util::SnarkyRng;
+pub use util::ImportError;
+
+use ark_ec::AffineCurve;
+use ark_std::rand::Rng as ArkRng;
+use num_traits::identities::Zero;
+
+type G1 = G1Elem;
+type G2 = G2Elem;
+
+
+// Maximum n supported by the given numbers of powers in G1 and G2
+pub fn max_degree(nr_1: usize, nr_2: usize) -> usize {
+    std::cmp::min(nr_1, nr_2).div_ceil(2)
+}
+
+// Number of powers [x^i]_1, [x^i]_2 needed by the QAP
+pub fn required_powers(qap: &ConstraintSystem) -> usize {
+    let (_, n, _) = qap.shape();
+    2 * n - 1
+}
+
+pub(crate) fn check_capacity(qap: &ConstraintSystem, nr_1: usize, nr_2: usize)
+    -> Result<(), ImportError>
+{
+    let required = required_powers(qap);
+    let available = std::cmp::min(nr_1, nr_2);
+    match available < required {
+        true    => Err(ImportError::InsufficientPowers { available, required }),
+        _       => Ok(())
+    }
+}
+
+// Checks that the provided powers are successive powers [x^i]_1, [x^i]_2
+// of the same non-zero x, by the pairing equations of verification step 7
+// over random coefficients. Assumes that they start from the generators.
+pub(crate) fn check_powers<R: SnarkyRng>(powers_1: &[G1], powers_2: &[G2], rng: &mut R)
+    -> Result<(), ImportError>
+{
+    let len = std::cmp::min(powers_1.len(), powers_2.len());
+    if len < 2 {
+        return Ok(());                  // Only the generators
+    }
+    if powers_1[1].is_zero() || powers_2[1].is_zero() {
+        return Err(ImportError::InvalidPowers);
+    }
+    let s = (0..len)
+        .map(|_| rscalar!(rng))
+        .collect::<Vec<Scalar>>();
+    let a = msm1!(powers_1[1..len], s[1..len]);
+    let b = msm2!(powers_2[1..len], s[1..len]);
+    let c = msm1!(powers_1[..len - 1], s[1..len]);
+    let valid =
+        multi_pairing_eq!((a, genG2!()), (-genG1!(), b)) &
+        multi_pairing_eq!((a, genG2!()), (-c, powers_2[1]));
+    match valid {
+        false   => Err(ImportError::InvalidPowers),
+        _       => Ok(())
+    }
+}
+
+// Builds the universal SRS from the provided powers (truncated to the size
+// needed by the QAP), along with the empty batch of update-proofs to be
+// extended by phase 1
+pub(crate) fn universal_from_powers<R: SnarkyRng>(
+    qap: &ConstraintSystem,
+    powers_1: &[G1],
+    powers_2: &[G2],
+    rng: &mut R,
+) -> Result<(UniversalSRS, BatchProof), ImportError>
+{
+    let (_, n, _) = qap.shape();
+    check_capacity(qap, powers_1.len(), powers_2.len())?;
+    if ct_ne!(powers_1[0], genG1!()) || ct_ne!(powers_2[0], genG2!()) {
+        return Err(ImportError::InvalidPowers);
+    }
+    check_powers(&powers_1[..2 * n - 1], &powers_2[..2 * n - 1], rng)?;
+
+    let c1 = (0..2 * n - 1)
+        .map(|i| (powers_1[i], powers_2[i]))
+        .collect();
+    let c2 = (0..n)
+        .map(|i| (powers_1[i], powers_1[i], powers_2[i], powers_2[i]))
+        .collect();
+    Ok((UniversalSRS { u: (c1, c2), pending_update: true }, BatchProof::initiate()))
+}
+
+pub(crate) fn check_point_1(elem: G1) -> Result<G1, ImportError> {
+    match elem.is_zero() || contained_in_group!(elem) {
+        false   => Err(ImportError::InvalidPoint),
+        _       => Ok(elem)
+    }
+}
+
+pub(crate) fn check_point_2(elem: G2) -> Result<G2, ImportError> {
+    match elem.is_zero() || contained_in_group!(elem) {
+        false   => Err(ImportError::InvalidPoint),
+        _       => Ok(elem)
+    }
+}
@@ -0,0 +1,190 @@
+//! Importer of snarkjs Powers-of-Tau files (`.ptau`) over the backend
+//! curve (snarkjs `bls12381` or, with the `bn254` feature, `bn128`).
+//!
+//! Layout: magic `ptau` | version (u32) | number of sections (u32), followed
+//! by sections of the form type (u32) | size (u64) | data. Integers are
+//! little-endian; base field elements are little-endian in Montgomery form.
+
+use crate::import::*;
+use crate::prover::BatchProof;
+use crate::srs::UniversalSRS;
+use circuits::ConstraintSystem;
+use util::SnarkyRng;
+use backend::*;
+
+use num_traits::identities::Zero;
+use backend::curve::{Fq, Fq2};
+use ark_ff::{BigInteger, FpParameters, FromBytes, PrimeField};
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::{Read, Seek, SeekFrom};
+
+pub const MAGIC: [u8; 4] = *b"ptau";
+pub const VERSION: u32 = 1;
+
+pub const SECTION_HEADER: u32 = 1;
+pub const SECTION_TAU_G1: u32 = 2;
+pub const SECTION_TAU_G2: u32 = 3;
+
+type Repr = <Fq as PrimeField>::BigInt;
+
+const N8: usize = std::mem::size_of::<Repr>();     // Byte size of base field elements
+
+
+// Header section of a .ptau file
+#[derive(Debug, PartialEq)]
+pub struct PtauHeader {
+    pub power: u32,                     // 2^power powers of tau in G2
+    pub ceremony_power: u32,
+}
+
+impl PtauHeader {
+
+    // Numbers of powers [x^i]_1, [x^i]_2 contained in the file
+    pub fn nr_powers(&self) -> (usize, usize) {
+        let size = 1usize << self.power;
+        (2 * size - 1, size)
+    }
+
+    // Maximum n supported by the file
+    pub fn max_degree(&self) -> usize {
+        let (nr_1, nr_2) = self.nr_powers();
+        max_degree(nr_1, nr_2)
+    }
+}
+
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, ImportError> {
+    let mut buffer = [0u8; 4];
+    reader.read_exact(&mut buffer)?;
+    Ok(u32::from_le_bytes(buffer))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, ImportError> {
+    let mut buffer = [0u8; 8];
+    reader.read_exact(&mut buffer)?;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+fn read_fq<R: Read>(reader: &mut R) -> Result<Fq, ImportError> {
+    let mut buffer = [0u8; N8];
+    reader.read_exact(&mut buffer)?;
+    let repr = Repr::read(&buffer[..])?;
+    match repr < <Fq as PrimeField>::Params::MODULUS {
+        true    => Ok(Fq::new(repr)),       // Montgomery form
+        false   => Err(ImportError::InvalidPoint),
+    }
+}
+
+fn read_point_1<R: Read>(reader: &mut R) -> Result<G1Elem, ImportError> {
+    let x = read_fq(reader)?;
+    let y = read_fq(reader)?;
+    let infinity = x.is_zero() && y.is_zero();
+    check_point_1(G1Elem::new(x, y, infinity))
+}
+
+fn read_point_2<R: Read>(reader: &mut R) -> Result<G2Elem, ImportError> {
+    let x = Fq2::new(read_fq(reader)?, read_fq(reader)?);
+    let y = Fq2::new(read_fq(reader)?, read_fq(reader)?);
+    let infinity = x.is_zero() && y.is_zero();
+    check_point_2(G2Elem::new(x, y, infinity))
+}
+
+
+// Maps section types to (offset, size) pairs
+fn read_sections<R: Read + Seek>(reader: &mut R)
+    -> Result<HashMap<u32, (u64, u64)>, ImportError>
+{
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC || read_u32(reader)? != VERSION {
+        return Err(ImportError::InvalidFormat);
+    }
+    let nr_sections = read_u32(reader)?;
+    let mut sections = HashMap::new();
+    for _ in 0..nr_sections {
+        let kind = read_u32(reader)?;
+        let size = read_u64(reader)?;
+        let offset = reader.stream_position()?;
+        if sections.insert(kind, (offset, size)).is_some() {
+            return Err(ImportError::InvalidFormat);
+        }
+        let skip = i64::try_from(size).map_err(|_| ImportError::InvalidFormat)?;
+        reader.seek(SeekFrom::Current(skip))?;
+    }
+    Ok(sections)
+}
+
+fn seek_section<R: Read + Seek>(
+    reader: &mut R,
+    sections: &HashMap<u32, (u64, u64)>,
+    kind: u32,
+    expected_size: u64,
+) -> Result<(), ImportError> {
+    let &(offset, size) = sections
+        .get(&kind)
+        .ok_or(ImportError::MissingSection(kind))?;
+    if size != expected_size {
+        return Err(ImportError::InvalidFormat);
+    }
+    reader.seek(SeekFrom::Start(offset))?;
+    Ok(())
+}
+
+fn parse_header<R: Read + Seek>(reader: &mut R, sections: &HashMap<u32, (u64, u64)>)
+    -> Result<PtauHeader, ImportError>
+{
+    let &(offset, _) = sections
+        .get(&SECTION_HEADER)
+        .ok_or(ImportError::MissingSection(SECTION_HEADER))?;
+    reader.seek(SeekFrom::Start(offset))?;
+    if read_u32(reader)? as usize != N8 {
+        return Err(ImportError::FieldMismatch);
+    }
+    let mut buffer = [0u8; N8];
+    reader.read_exact(&mut buffer)?;
+    let modulus = <Fq as PrimeField>::Params::MODULUS.to_bytes_le();
+    if buffer[..] != modulus[..] {
+        return Err(ImportError::FieldMismatch);
+    }
+    let power = read_u32(reader)?;
+    let ceremony_power = read_u32(reader)?;
+    if power >= 32 {
+        return Err(ImportError::InvalidFormat);
+    }
+    Ok(PtauHeader { power, ceremony_power })
+}
+
+
+pub fn read_header<R: Read + Seek>(reader: &mut R) -> Result<PtauHeader, ImportError> {
+    let sections = read_sections(reader)?;
+    parse_header(reader, &sections)
+}
+
+// Builds the universal SRS of the QAP degree from the powers of tau in
+// the file, along with the (initially empty) batch of phase 1 update-proofs.
+// Fails with InvalidPowers if these are not successive powers of tau.
+pub fn import<R: Read + Seek, G: SnarkyRng>(reader: &mut R, qap: &ConstraintSystem, rng: &mut G)
+    -> Result<(UniversalSRS, BatchProof), ImportError>
+{
+    let sections = read_sections(reader)?;
+    let header = parse_header(reader, &sections)?;
+    let (nr_1, nr_2) = header.nr_powers();
+    check_capacity(qap, nr_1, nr_2)?;
+    let required = required_powers(qap);
+
+    seek_section(reader, &sections, SECTION_TAU_G1, (nr_1 * 2 * N8) as u64)?;
+    let mut powers_1 = Vec::with_capacity(required);
+    for _ in 0..required {
+        powers_1.push(read_point_1(reader)?);
+    }
+
+    seek_section(reader, &sections, SECTION_TAU_G2, (nr_2 * 4 * N8) as u64)?;
+    let mut powers_2 = Vec::with_capacity(required);
+    for _ in 0..required {
+        powers_2.push(read_point_2(reader)?);
+    }
+
+    universal_from_powers(qap, &powers_1, &powers_2, rng)
+}
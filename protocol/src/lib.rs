@@ -6,8 +6,20 @@ pub mod srs;
 pub mod prover;
 pub mod updater;
 pub mod verifier;
+pub mod serialize;
+pub mod transcript;
+pub mod import;
+pub mod groth16;
+pub mod ceremony;
 
-pub use srs::{Trapdoor, SRS};
+pub use srs::{Trapdoor, SRS, SRSHeader, UniversalSRS};
 pub use prover::BatchProof;
-pub use updater::{Phase, update};
-pub use verifier::{Verification, verify, verify_naive};
+pub use updater::{Phase, update, update_universal};
+pub use verifier::{
+    Verification, VerificationStep, StepOutcome, VerificationReport,
+    verify, verify_batched, verify_detailed, verify_naive, verify_update, verify_update_universal,
+};
+pub use serialize::{Encoding, SerializationError};
+pub use transcript::{Transcript, TranscriptError};
+pub use ceremony::{Ceremony, Stage, CeremonyError};
+pub use util::{SnarkyError, ShapeError, ProofError};
@@ -1,52 +1,60 @@
-use sha2::Digest;
-use std::convert::TryInto;
-use std::io::Cursor;
 use num_traits::identities::{Zero, One};
 
 use ark_ec::{AffineCurve, PairingEngine};
-use ark_ff::FromBytes;
 use ark_ff::ToBytes;
 
 use crate::updater::Phase;
-use crate::srs::SRS;
+use crate::srs::{SRS, U};
+use crate::serialize::*;
 use backend::*;
+pub use util::ProofError;
+use util::{HashError, ShapeError, SnarkyError};
+use backend::multi_pairing::{Equation, multi_pairing_eq};
 
 use rayon::prelude::*;
+use std::fmt;
+use std::io::{Read, Write};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+use backend::ct::ConstantTimeEq;
+use subtle::Choice;
 
 type G1 = G1Elem;
 type G2 = G2Elem;
 
 
-// Indicates proof-verification failure
-#[derive(Debug, PartialEq)]
-pub enum ProofError {
-    DlogFailure,
-    RhoFailure,
-    BatchFailure,
-}
-
-
 pub struct Dlog;
 type Commitment = (G1, G2);
 
+// Domain separation tag of the random oracle
+backend::cfg_bls12_381! {
+    pub const DLOG_DST: &[u8] = b"SNARKY-V01-CS01-DLOG-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+}
+backend::cfg_bn254! {
+    pub const DLOG_DST: &[u8] = b"SNARKY-V01-CS01-DLOG-with-BN254G1_XMD:SHA-256_SVDW_RO_";
+}
+
 impl Dlog {
 
-    pub fn rndoracle(c: &Commitment) -> G1 {
+    // Hash-to-curve of the commitment, so that its discrete logarithm is
+    // unknown. Fails only upon invalid DLOG_DST
+    pub fn rndoracle(c: &Commitment) -> Result<G1, HashError> {
         let bytes = [bytes1!(c.0), bytes2!(c.1)].concat();
-        hashG1!(&bytes)
+        hashG1!(&bytes, DLOG_DST)
     }
     
-    pub fn prove(c: &Commitment, witness: Scalar) -> G1 {
-        smul1!(witness, Self::rndoracle(&c))
+    // Witness passed by reference, so that no copy outlives the caller's
+    pub fn prove(c: &Commitment, witness: &Scalar) -> Result<G1, HashError> {
+        Ok(smul1!(*witness, Self::rndoracle(&c)?))
     }
 
     pub fn verify(ctx: (&G1, &G2), c: &Commitment, prf: &G1) 
         -> Result<bool, ProofError> 
     {
         let (G, H) = ctx;
+        let oracle = Self::rndoracle(&c).map_err(|_| ProofError::DlogFailure)?;
         match 
             ct_eq!(pair!(c.0, *H), pair!(*G, c.1)) &
-            ct_eq!(pair!(*prf, *H), pair!(Self::rndoracle(&c), c.1))
+            ct_eq!(pair!(*prf, *H), pair!(oracle, c.1))
         {
             false   => Err(ProofError::DlogFailure),
             _       => Ok(true)
@@ -56,7 +64,7 @@ impl Dlog {
 
 
 // PoKs for scalars used in SRS update
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RhoProof {
     pub aux: G1, 
     pub com: Commitment,
@@ -65,21 +73,25 @@ pub struct RhoProof {
 
 impl RhoProof {
     
-    pub fn create(ctx: (&G1, &G2), base: &G1, w: &Scalar) -> Self {
+    pub fn create(ctx: (&G1, &G2), base: &G1, w: &Scalar) -> Result<Self, HashError> {
         let (G, H) = ctx;
         let aux = smul1!(*w, *base);
         let com = (smul1!(*w, *G), smul2!(*w, *H));
-        Self { 
+        Ok(Self { 
             aux, 
             com, 
-            prf: Dlog::prove(&com, *w),
-        }
+            prf: Dlog::prove(&com, w)?,
+        })
     }
 
     pub fn verify(&self, ctx: (&G1, &G2), rho: Option<&Self>) -> Result<bool, ProofError> {
         // TODO: ARK: Simplify signatures
         let (G, H) = ctx;
 
+        if !self.is_well_formed() {
+            return Err(ProofError::RhoFailure);
+        }
+
         let out1 = Dlog::verify(ctx, &self.com, &self.prf).unwrap_or(false);
         let out2 = match rho {
             Some(rho) => {
@@ -93,6 +105,37 @@ impl RhoProof {
             _       => Ok(true)
         }
     }
+
+    // All elements in the prime order subgroup
+    pub fn is_well_formed(&self) -> bool {
+        contained_in_group!(self.aux) &
+        contained_in_group!(self.com.0) &
+        contained_in_group!(self.com.1) &
+        contained_in_group!(self.prf)
+    }
+
+    // aux | com.0 | com.1 | prf
+    pub fn write<W: Write>(&self, writer: &mut W, encoding: Encoding)
+        -> Result<(), SerializationError>
+    {
+        write_g1(writer, &self.aux, encoding)?;
+        write_g1(writer, &self.com.0, encoding)?;
+        write_g2(writer, &self.com.1, encoding)?;
+        write_g1(writer, &self.prf, encoding)?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: &mut R, encoding: Encoding)
+        -> Result<Self, SerializationError>
+    {
+        let aux = read_g1(reader, encoding)?;
+        let com = (
+            read_g1(reader, encoding)?,
+            read_g2(reader, encoding)?,
+        );
+        let prf = read_g1(reader, encoding)?;
+        Ok(Self { aux, com, prf })
+    }
 }
 
 
@@ -101,39 +144,109 @@ impl RhoProof {
 // Note: No verify functionality is possible at this
 // level since each update-proof is verified against
 // its previous one in the containing batch.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum UpdateProof {
     ONE(RhoProof, RhoProof, RhoProof),
     TWO(RhoProof),
 }
 
-#[derive(Debug, PartialEq)]
+// Toxic waste of an update; zeroized on drop, never printed and compared only
+// in constant time
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub enum Witness {
     ONE(Scalar, Scalar, Scalar),
     TWO(Scalar),
 }
 
+impl fmt::Debug for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Witness::ONE(..) => write!(f, "Witness::ONE(<redacted>)"),
+            Witness::TWO(..) => write!(f, "Witness::TWO(<redacted>)"),
+        }
+    }
+}
+
+// Witnesses of different phases are unequal
+impl ConstantTimeEq for Witness {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        match (self, other) {
+            (Witness::ONE(a1, b1, x1), Witness::ONE(a2, b2, x2)) => {
+                a1.ct_eq(a2) & b1.ct_eq(b2) & x1.ct_eq(x2)
+            },
+            (Witness::TWO(d1), Witness::TWO(d2)) => d1.ct_eq(d2),
+            _ => Choice::from(0),
+        }
+    }
+}
+
 impl UpdateProof {
-    pub fn create(srs: &SRS, w: &Witness) -> Self{
+    pub fn create(srs: &SRS, w: &Witness) -> Result<Self, SnarkyError> {
         let (G, H) = (genG1!(), genG2!());
         match w {
-            Witness::ONE(a, b, x) => {
-                // phase 1, step 3-6
-                let srs_u = &srs.u;
-                UpdateProof::ONE(
-                    RhoProof::create((&G, &H), &srs_u.1[0].0, &a),
-                    RhoProof::create((&G, &H), &srs_u.1[0].1, &b),
-                    RhoProof::create((&G, &H), &srs_u.0[1].0, &x),
-                )
-            },
+            Witness::ONE(a, b, x) => Self::create_one(&srs.u, (a, b, x)),
             Witness::TWO(d) => {
                 // phase 2, step 3-4
                 let srs_s = &srs.s;
-                UpdateProof::TWO(
-                    RhoProof::create((&G, &H), &srs_s.0, &d)
-                )
+                Ok(UpdateProof::TWO(
+                    RhoProof::create((&G, &H), &srs_s.0, &d)?
+                ))
+            },
+        }
+    }
+
+    // phase 1, step 3-6; used also against the universal SRS. Fails if the
+    // u-component is of degree less than 2
+    pub(crate) fn create_one(srs_u: &U, w: (&Scalar, &Scalar, &Scalar))
+        -> Result<Self, SnarkyError>
+    {
+        let gens = (genG1!(), genG2!());
+        let ctx = (&gens.0, &gens.1);
+        let (a, b, x) = w;
+        let (base_ab, base_x) = match (srs_u.1.first(), srs_u.0.get(1)) {
+            (Some(base_ab), Some(base_x)) => (base_ab, base_x),
+            _ => return Err(ShapeError::TooFewConstraints.into()),
+        };
+        Ok(UpdateProof::ONE(
+            RhoProof::create(ctx, &base_ab.0, a)?,
+            RhoProof::create(ctx, &base_ab.1, b)?,
+            RhoProof::create(ctx, &base_x.0, x)?,
+        ))
+    }
+
+    // phase (1 byte) | rho-proofs
+    pub fn write<W: Write>(&self, writer: &mut W, encoding: Encoding)
+        -> Result<(), SerializationError>
+    {
+        match self {
+            UpdateProof::ONE(r1, r2, r3) => {
+                write_u8(writer, Phase::ONE as u8)?;
+                for rho in [r1, r2, r3].iter() {
+                    rho.write(writer, encoding)?;
+                }
+            },
+            UpdateProof::TWO(r) => {
+                write_u8(writer, Phase::TWO as u8)?;
+                r.write(writer, encoding)?;
             },
         }
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: &mut R, encoding: Encoding)
+        -> Result<Self, SerializationError>
+    {
+        match read_u8(reader)? {
+            1 => Ok(UpdateProof::ONE(
+                RhoProof::read(reader, encoding)?,
+                RhoProof::read(reader, encoding)?,
+                RhoProof::read(reader, encoding)?,
+            )),
+            2 => Ok(UpdateProof::TWO(
+                RhoProof::read(reader, encoding)?,
+            )),
+            _ => Err(SerializationError::InvalidPhase),
+        }
     }
 }
 
@@ -154,6 +267,42 @@ impl BatchProof {
         }
     }
 
+    // header | batch_1 | batch_2
+    pub fn write<W: Write>(&self, writer: &mut W, encoding: Encoding)
+        -> Result<(), SerializationError>
+    {
+        write_preamble(writer, &BATCH_MAGIC, encoding)?;
+        write_u64(writer, self.batch_1.len() as u64)?;
+        write_u64(writer, self.batch_2.len() as u64)?;
+        for rhos in self.batch_1.iter() {
+            for rho in rhos.iter() {
+                rho.write(writer, encoding)?;
+            }
+        }
+        for rho in self.batch_2.iter() {
+            rho.write(writer, encoding)?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+        let (_, encoding) = read_preamble(reader, &BATCH_MAGIC)?;
+        let len1 = read_u64(reader)?;
+        let len2 = read_u64(reader)?;
+        let mut batch = Self::initiate();
+        for _ in 0..len1 {
+            batch.batch_1.push([
+                RhoProof::read(reader, encoding)?,
+                RhoProof::read(reader, encoding)?,
+                RhoProof::read(reader, encoding)?,
+            ]);
+        }
+        for _ in 0..len2 {
+            batch.batch_2.push(RhoProof::read(reader, encoding)?);
+        }
+        Ok(batch)
+    }
+
     pub fn append(&mut self, proof: UpdateProof) {
         match proof {
             UpdateProof::ONE(r1, r2, r3) => {
@@ -229,7 +378,7 @@ impl BatchProof {
                 
                 // step 9
                 let out2 = {
-                    ct_eq!(pair!(srs_s.0, H), pair!(G, srs_s.1)) &
+                    multi_pairing_eq!((srs_s.0, H), (-G, srs_s.1)) &
                     {
                         let len = batch_s.len();
                         match len > 0 {
@@ -250,169 +399,213 @@ impl BatchProof {
         }
     }
 
-    pub fn verify(&self, srs: &SRS, s: &[backend::Scalar], phase: Phase) -> Result<bool, ProofError> {
+    // Pairing equations of steps 4-5 (resp. 10-12), each holding iff the
+    // product of its pairings is the unit, along with the outcome of the
+    // remaining checks of step 6 (resp. 12)
+    pub fn equations(&self, srs: &SRS, s: &[backend::Scalar], phase: Phase)
+        -> (Vec<Equation>, bool)
+    {
         let (G, H) = (genG1!(), genG2!());
-        let zero = zeroG1!();
+        let mut equations = Vec::<Equation>::new();
+        if s.len() < self.len(phase) {
+            return (equations, false);              // Not enough scalars
+        }
         match phase {
             Phase::ONE => {
                 let batch_u = &self.batch_1;
-                let srs_u = &srs.u;
 
                 // step 4-5
-                let mut out1 = true;
                 for j in 0..3 {
                     if batch_u.len() > 2 {
-                        let (A, B) = (2..batch_u.len())
+                        let A = msm1!(
+                            (2..batch_u.len())
+                                .map(|i| batch_u[i][j].aux)                     // 4
+                                .collect::<Vec<G1>>(),
+                            s[2..batch_u.len()]
+                        );
+                        let mut pairs = (2..batch_u.len())
                             .into_par_iter()
-                            .map(|i| {                                          // 4
+                            .map(|i| {
                                 let rho      = &batch_u[i][j];
                                 let rho_prev = &batch_u[i - 1][j];
-                                (
-                                    smul1!(s[i], rho.aux),
-                                    pair!(smul1!(s[i], rho_prev.aux), rho.com.1)
-                                )
+                                (smul1!(s[i], rho_prev.aux), rho.com.1)
                             })
-                            .reduce(|| (zeroG1!(), unit!()), 
-                                |
-                                    (a1, b1),
-                                    (a2, b2),
-                                |
-                                (
-                                    a1 + a2,
-                                    b1 * b2,
-                                )
-                            );
-                        out1 = out1 & ct_eq!(pair!(A, H), B);                   // 5.(a)
+                            .collect::<Vec<(G1, G2)>>();
+                        pairs.push((-A, H));
+                        equations.push(pairs);                                  // 5.(a)
                     }
                     if batch_u.len() > 1 {
-                        let (C, D, E, F) = (1..batch_u.len())
-                            .into_par_iter()
-                            .map(|i| {
-                                let rho      = &batch_u[i][j];                  // 4
-                                let rho_prev = &batch_u[i - 1][j];
-                                let R = Dlog::rndoracle(&rho.com);
-                                (
-                                    smul1!(s[i], rho.com.0),
-                                    smul2!(s[i], rho.com.1),
-                                    smul1!(s[i], rho.prf),
-                                    pair!(smul1!(s[i], R), rho.com.1),
-                                )
-                            })
-                            .reduce(|| (zeroG1!(), zeroG2!(), zeroG1!(), unit!()), 
-                                |
-                                    (a1, b1, c1, d1),
-                                    (a2, b2, c2, d2),
-                                |
-                                (
-                                    a1 + a2,
-                                    b1 + b2,
-                                    c1 + c2,
-                                    d1 * d2,
-                                )
-                            );
-                        out1 = out1 & ct_eq!(pair!(C, H), pair!(G, D));         // 5.(b)
-                                    & ct_eq!(pair!(E, H), F);                   // 5.(c)
+                        let rhos = (1..batch_u.len())                           // 4
+                            .map(|i| &batch_u[i][j])
+                            .collect::<Vec<&RhoProof>>();
+                        let C = msm1!(rhos.iter().map(|rho| rho.com.0).collect::<Vec<G1>>(), s[1..]);
+                        let D = msm2!(rhos.iter().map(|rho| rho.com.1).collect::<Vec<G2>>(), s[1..]);
+                        let E = msm1!(rhos.iter().map(|rho| rho.prf).collect::<Vec<G1>>(), s[1..]);
+                        let oracles = match Self::oracles(&rhos) {
+                            Some(oracles)   => oracles,
+                            None            => return (equations, false),
+                        };
+                        let mut pairs = rhos
+                            .par_iter()
+                            .zip(s[1..].par_iter())
+                            .zip(oracles.par_iter())
+                            .map(|((rho, &s_i), R)| (smul1!(s_i, *R), rho.com.1))
+                            .collect::<Vec<(G1, G2)>>();
+                        pairs.push((-E, H));
+                        equations.push(vec![(C, H), (-G, D)]);                  // 5.(b)
+                        equations.push(pairs);                                  // 5.(c)
                     }
                 }
 
                 // step 6
-                let len = batch_u.len();
-                let out2 = match len > 0 {
-                    false   => true,
-                    true    => {
-                        ct_eq!(srs_u.0[1].0, batch_u[len - 1][2].aux) &
-                        ct_eq!(srs_u.1[0].0, batch_u[len - 1][0].aux) &
-                        ct_eq!(srs_u.1[0].1, batch_u[len - 1][1].aux) &
-                        ct_ne!(batch_u[len - 1][2].aux, zero) &
-                        ct_ne!(batch_u[len - 1][0].aux, zero) &
-                        ct_ne!(batch_u[len - 1][1].aux, zero)
-                    }
-                }; 
-                
-                match out1 & out2 {
-                    false   => Err(ProofError::BatchFailure),
-                    _       => Ok(true)
-                }
+                let well_formed = batch_u
+                    .par_iter()
+                    .flat_map(|rhos| rhos.par_iter())
+                    .map(|rho| rho.is_well_formed())
+                    .reduce(|| true, |acc, b| acc & b);
+                (equations, well_formed & self.matches_srs(srs, Phase::ONE))
             },
             Phase::TWO => {
                 let batch_s = &self.batch_2;
                 let srs_s = &srs.s;
 
                 // step 10-11
-                let mut out1 = true;
                 if batch_s.len() > 2 {
-                    let (A, B) = (2..batch_s.len())
+                    let A = msm1!(
+                        (2..batch_s.len())
+                            .map(|i| batch_s[i].aux)                            // 10
+                            .collect::<Vec<G1>>(),
+                        s[2..batch_s.len()]
+                    );
+                    let mut pairs = (2..batch_s.len())
                         .into_par_iter()
-                        .map(|i| {                                          // 10
+                        .map(|i| {
                             let rho      = &batch_s[i];
                             let rho_prev = &batch_s[i - 1];
-                            (
-                                smul1!(s[i], rho.aux),
-                                pair!(smul1!(s[i], rho_prev.aux), rho.com.1)
-                            )
+                            (smul1!(s[i], rho_prev.aux), rho.com.1)
                         })
-                        .reduce(|| (zeroG1!(), unit!()), 
-                            |
-                                (a1, b1),
-                                (a2, b2),
-                            |
-                            (
-                                a1 + a2,
-                                b1 * b2,
-                            )
-                        );
-                    out1 = out1 & ct_eq!(pair!(A, H), B);                   // 11.(a)
+                        .collect::<Vec<(G1, G2)>>();
+                    pairs.push((-A, H));
+                    equations.push(pairs);                                  // 11.(a)
                 }
                 if batch_s.len() > 1 {
-                    let (C, D, E, F) = (1..batch_s.len())
-                        .into_par_iter()
-                        .map(|i| {
-                            let rho      = &batch_s[i];                     // 10
-                            let rho_prev = &batch_s[i - 1];
-                            let R = Dlog::rndoracle(&rho.com);
-                            (
-                                smul1!(s[i], rho.com.0),
-                                smul2!(s[i], rho.com.1),
-                                smul1!(s[i], rho.prf),
-                                pair!(smul1!(s[i], R), rho.com.1),
-                            )
-                        })
-                        .reduce(|| (zeroG1!(), zeroG2!(), zeroG1!(), unit!()), 
-                            |
-                                (a1, b1, c1, d1),
-                                (a2, b2, c2, d2),
-                            |
-                            (
-                                a1 + a2,
-                                b1 + b2,
-                                c1 + c2,
-                                d1 * d2,
-                            )
-                        );
-                    out1 = out1 & ct_eq!(pair!(C, H), pair!(G, D));         // 11.(b)
-                                & ct_eq!(pair!(E, H), F);                   // 11.(c)
+                    let rhos = &batch_s[1..];                               // 10
+                    let C = msm1!(rhos.iter().map(|rho| rho.com.0).collect::<Vec<G1>>(), s[1..]);
+                    let D = msm2!(rhos.iter().map(|rho| rho.com.1).collect::<Vec<G2>>(), s[1..]);
+                    let E = msm1!(rhos.iter().map(|rho| rho.prf).collect::<Vec<G1>>(), s[1..]);
+                    let oracles = match Self::oracles(&rhos.iter().collect::<Vec<&RhoProof>>()) {
+                        Some(oracles)   => oracles,
+                        None            => return (equations, false),
+                    };
+                    let mut pairs = rhos
+                        .par_iter()
+                        .zip(s[1..].par_iter())
+                        .zip(oracles.par_iter())
+                        .map(|((rho, &s_i), R)| (smul1!(s_i, *R), rho.com.1))
+                        .collect::<Vec<(G1, G2)>>();
+                    pairs.push((-E, H));
+                    equations.push(vec![(C, H), (-G, D)]);                  // 11.(b)
+                    equations.push(pairs);                                  // 11.(c)
                 }
- 
+
                 // step 12
-                let out2 = {
-                    ct_eq!(pair!(srs_s.0, H), pair!(G, srs_s.1)) &
-                    {
-                        let len = batch_s.len();
-                        match len > 0 {
-                            false   => true,
-                            true    => {
-                                ct_eq!(srs_s.0, batch_s[len - 1].aux) &
-                                ct_ne!(batch_s[len - 1].aux, zero)
-                            }
-                        }
-                    }
-                };
+                equations.push(vec![(srs_s.0, H), (-G, srs_s.1)]);
+                let well_formed = batch_s
+                    .par_iter()
+                    .map(|rho| rho.is_well_formed())
+                    .reduce(|| true, |acc, b| acc & b);
+                (equations, well_formed & self.matches_srs(srs, Phase::TWO))
+            }
+        }
+    }
 
-                match out1 & out2 {
-                    false   => return Err(ProofError::BatchFailure),
-                    _       => Ok(true)
+    // Random oracle values of the provided rho-proofs; None upon failure
+    fn oracles(rhos: &[&RhoProof]) -> Option<Vec<G1>> {
+        rhos.par_iter()
+            .map(|rho| Dlog::rndoracle(&rho.com).ok())
+            .collect()
+    }
+
+    // Whether the last update-proof of the provided phase (if any) matches
+    // the SRS, i.e., the remaining checks of step 6 (resp. 12)
+    fn matches_srs(&self, srs: &SRS, phase: Phase) -> bool {
+        let zero = zeroG1!();
+        match phase {
+            Phase::ONE => {
+                let (batch_u, srs_u) = (&self.batch_1, &srs.u);
+                match (batch_u.last(), srs_u.0.get(1), srs_u.1.first()) {
+                    (None, _, _)                    => true,
+                    (Some(last), Some(p), Some(q))  => {
+                        ct_eq!(p.0, last[2].aux) &
+                        ct_eq!(q.0, last[0].aux) &
+                        ct_eq!(q.1, last[1].aux) &
+                        ct_ne!(last[2].aux, zero) &
+                        ct_ne!(last[0].aux, zero) &
+                        ct_ne!(last[1].aux, zero)
+                    },
+                    _                               => false,
                 }
-            }
+            },
+            Phase::TWO => {
+                match self.batch_2.last() {
+                    None        => true,
+                    Some(last)  => {
+                        ct_eq!(srs.s.0, last.aux) &
+                        ct_ne!(last.aux, zero)
+                    }
+                }
+            },
+        }
+    }
+
+    // Number of update-proofs of the provided phase
+    pub fn len(&self, phase: Phase) -> usize {
+        match phase {
+            Phase::ONE => self.batch_1.len(),
+            Phase::TWO => self.batch_2.len(),
+        }
+    }
+
+    // Index of the first update-proof of the provided phase that fails to
+    // verify against its predecessor or, if there is none, that of the last
+    // one in case it does not match the SRS. Checks each update-proof
+    // separately; use only for locating failures
+    pub fn locate_failure(&self, srs: &SRS, phase: Phase) -> Option<usize> {
+        let gens = (genG1!(), genG2!());
+        let ctx = (&gens.0, &gens.1);
+        let invalid = match phase {
+            Phase::ONE => {
+                let batch_u = &self.batch_1;
+                (0..batch_u.len()).find(|&i| {
+                    (0..3).any(|j| {
+                        let prev = if i > 0 { Some(&batch_u[i - 1][j]) } else { None };
+                        batch_u[i][j].verify(ctx, prev).is_err()
+                    })
+                })
+            },
+            Phase::TWO => {
+                let batch_s = &self.batch_2;
+                (0..batch_s.len()).find(|&i| {
+                    let prev = if i > 0 { Some(&batch_s[i - 1]) } else { None };
+                    batch_s[i].verify(ctx, prev).is_err()
+                })
+            },
+        };
+        invalid.or_else(|| match self.matches_srs(srs, phase) {
+            false   => Some(self.len(phase) - 1),
+            _       => None
+        })
+    }
+
+    pub fn verify(&self, srs: &SRS, s: &[backend::Scalar], phase: Phase) -> Result<bool, ProofError> {
+        let (equations, out2) = self.equations(srs, s, phase);
+        let out1 = equations
+            .par_iter()
+            .map(|pairs| multi_pairing_eq(pairs))
+            .reduce(|| true, |acc, b| acc & b);
+        match out1 & out2 {
+            false   => Err(ProofError::BatchFailure),
+            _       => Ok(true)
         }
     }
 }
@@ -0,0 +1,107 @@
+use std::io::{Read, Write};
+use backend::*;
+pub use util::SerializationError;
+
+type G1 = G1Elem;
+type G2 = G2Elem;
+
+// Current version of the binary format
+pub const VERSION: u8 = 1;
+
+// Leading bytes of serialized objects
+pub const SRS_MAGIC: [u8; 4] = *b"SRS_";
+pub const BATCH_MAGIC: [u8; 4] = *b"BTCH";
+pub const TRANSCRIPT_MAGIC: [u8; 4] = *b"TRNS";
+pub const VKEY_MAGIC: [u8; 4] = *b"VKEY";
+
+
+// Point encoding in use (a single byte in the header)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Encoding {
+    COMPRESSED = 0,
+    UNCOMPRESSED = 1,
+}
+
+impl Encoding {
+    pub fn is_compressed(&self) -> bool {
+        match self {
+            Encoding::COMPRESSED    => true,
+            Encoding::UNCOMPRESSED  => false,
+        }
+    }
+}
+
+
+pub fn write_u8<W: Write>(writer: &mut W, val: u8) -> Result<(), SerializationError> {
+    writer.write_all(&[val])?;
+    Ok(())
+}
+
+pub fn read_u8<R: Read>(reader: &mut R) -> Result<u8, SerializationError> {
+    let mut buffer = [0u8; 1];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer[0])
+}
+
+pub fn write_u64<W: Write>(writer: &mut W, val: u64) -> Result<(), SerializationError> {
+    writer.write_all(&val.to_le_bytes())?;
+    Ok(())
+}
+
+pub fn read_u64<R: Read>(reader: &mut R) -> Result<u64, SerializationError> {
+    let mut buffer = [0u8; 8];
+    reader.read_exact(&mut buffer)?;
+    Ok(u64::from_le_bytes(buffer))
+}
+
+pub fn write_g1<W: Write>(writer: &mut W, elem: &G1, encoding: Encoding)
+    -> Result<(), SerializationError>
+{
+    write1!(&mut *writer, *elem, encoding.is_compressed())?;
+    Ok(())
+}
+
+pub fn read_g1<R: Read>(reader: &mut R, encoding: Encoding) -> Result<G1, SerializationError> {
+    Ok(read1!(&mut *reader, encoding.is_compressed())?)
+}
+
+pub fn write_g2<W: Write>(writer: &mut W, elem: &G2, encoding: Encoding)
+    -> Result<(), SerializationError>
+{
+    write2!(&mut *writer, *elem, encoding.is_compressed())?;
+    Ok(())
+}
+
+pub fn read_g2<R: Read>(reader: &mut R, encoding: Encoding) -> Result<G2, SerializationError> {
+    Ok(read2!(&mut *reader, encoding.is_compressed())?)
+}
+
+// magic (4 bytes) | version (1 byte) | encoding (1 byte)
+pub fn write_preamble<W: Write>(writer: &mut W, magic: &[u8; 4], encoding: Encoding)
+    -> Result<(), SerializationError>
+{
+    writer.write_all(magic)?;
+    write_u8(writer, VERSION)?;
+    write_u8(writer, encoding as u8)?;
+    Ok(())
+}
+
+pub fn read_preamble<R: Read>(reader: &mut R, magic: &[u8; 4])
+    -> Result<(u8, Encoding), SerializationError>
+{
+    let mut buffer = [0u8; 4];
+    reader.read_exact(&mut buffer)?;
+    if &buffer != magic {
+        return Err(SerializationError::InvalidMagic);
+    }
+    let version = read_u8(reader)?;
+    if version != VERSION {
+        return Err(SerializationError::InvalidVersion(version));
+    }
+    let encoding = match read_u8(reader)? {
+        0 => Encoding::COMPRESSED,
+        1 => Encoding::UNCOMPRESSED,
+        e => return Err(SerializationError::InvalidEncoding(e)),
+    };
+    Ok((version, encoding))
+}
@@ -1,16 +1,29 @@
 use backend::*;
+use backend::fixed_base::{FixedBase, powers};
 use circuits::ConstraintSystem;
+use util::{SnarkyRng, SnarkyError, ShapeError, ProofError, ImportError};
 use crate::prover::Witness;
+use crate::serialize::*;
 
-use num_traits::identities::{Zero, One};
+use num_traits::identities::One;
 use ark_ec::AffineCurve;
 use ark_ff::fields::Field;
 use ark_std::rand::Rng as ArkRng;
 
 use rayon::prelude::*;
+use std::fmt;
+use std::io::{Read, Write};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+use backend::ct::ConstantTimeEq;
+use subtle::Choice;
 
 
-#[derive(Debug, PartialEq)]
+// Secret scalar, zeroized on drop
+pub type Secret = Zeroizing<Scalar>;
+
+// Toxic waste (a, b, d, x) of the setup; zeroized on drop, never printed and
+// compared only in constant time
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub struct Trapdoor(
     pub Scalar,
     pub Scalar,
@@ -18,6 +31,19 @@ pub struct Trapdoor(
     pub Scalar,
 );
 
+impl fmt::Debug for Trapdoor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Trapdoor(<redacted>)")
+    }
+}
+
+impl ConstantTimeEq for Trapdoor {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0) & self.1.ct_eq(&other.1) &
+        self.2.ct_eq(&other.2) & self.3.ct_eq(&other.3)
+    }
+}
+
 
 impl Trapdoor {
 
@@ -29,17 +55,31 @@ impl Trapdoor {
         Self(one!(), one!(), one!(), one!())
     }
 
-    pub fn random() -> Self {
-        Self(
-            rscalar!(::util::snarky_rng()),
-            rscalar!(::util::snarky_rng()),
-            rscalar!(::util::snarky_rng()),
-            rscalar!(::util::snarky_rng()),
-        )
+    // The d component is redrawn in the (negligible) case that it is zero.
+    // Drawn in place, so that no copies are left behind
+    pub fn random<R: SnarkyRng>(rng: &mut R) -> Self {
+        Self::random_with_dinv(rng).0
     }
 
-    pub fn extract(&self) -> (Scalar, Scalar, Scalar, Scalar) {
-        (self.0, self.1, self.2, self.3)
+    // Along with the inverse of d
+    fn random_with_dinv<R: SnarkyRng>(rng: &mut R) -> (Self, Secret) {
+        let mut trp = Self(rscalar!(rng), rscalar!(rng), rscalar!(rng), rscalar!(rng));
+        loop {
+            match inv!(trp.2) {
+                Some(dinv)  => return (trp, Zeroizing::new(dinv)),
+                None        => trp.2 = rscalar!(rng),
+            }
+        }
+    }
+
+    // Copies of the secret values (a, b, d, x), zeroized on drop
+    pub fn extract(&self) -> (Secret, Secret, Secret, Secret) {
+        (
+            Zeroizing::new(self.0),
+            Zeroizing::new(self.1),
+            Zeroizing::new(self.2),
+            Zeroizing::new(self.3),
+        )
     }
 }
 
@@ -50,7 +90,7 @@ pub type U = (Vec<(G1, G2)>, Vec<(G1, G1, G2, G2)>);
 pub type S = (G1, G2, Vec<G1>, Vec<G1>);
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SRS {
     pub u: U,
     pub s: S,
@@ -59,140 +99,96 @@ pub struct SRS {
 impl SRS {
 
     pub fn setup_with_unit_trapdoor(qap: &ConstraintSystem) -> (Self, Trapdoor) {
-        SRS::setup(&qap, Some(Trapdoor::from_units()))
+        let trp = Trapdoor::from_units();
+        (SRS::create_unchecked(&trp, &one!(), &qap), trp)
     }
 
-    pub fn setup_with_random_trapdoor(qap: &ConstraintSystem) -> (Self, Trapdoor) {
-        SRS::setup(&qap, None)
+    pub fn setup_with_random_trapdoor<R: SnarkyRng>(qap: &ConstraintSystem, rng: &mut R)
+        -> (Self, Trapdoor)
+    {
+        let (trp, dinv) = Trapdoor::random_with_dinv(rng);
+        (SRS::create_unchecked(&trp, &dinv, &qap), trp)
     }
 
-    pub fn setup(qap: &ConstraintSystem, trapdoor: Option::<Trapdoor>) -> (Self, Trapdoor) {
+    // The provided RNG is used only if no trapdoor is provided
+    pub fn setup<R: SnarkyRng>(qap: &ConstraintSystem, trapdoor: Option::<Trapdoor>, rng: &mut R)
+        -> Result<(Self, Trapdoor), SnarkyError>
+    {
         let trp = match trapdoor {
             Some(trp) => trp,
-            None => Trapdoor::random()
+            None => Trapdoor::random(rng)
         };
-        let srs = SRS::create(&trp, &qap);
-        (srs, trp)
+        let srs = SRS::create(&trp, &qap)?;
+        Ok((srs, trp))
     }
 
-    pub fn create(trp: &Trapdoor, qap: &ConstraintSystem) -> Self {
-        Self {
-            u: Self::create_u(&trp, &qap),
-            s: Self::create_s(&trp, &qap),
-        }
+    // Fails if the d component of the trapdoor is zero
+    pub fn create(trp: &Trapdoor, qap: &ConstraintSystem) -> Result<Self, SnarkyError> {
+        let dinv = Zeroizing::new(inv!(trp.2).ok_or(SnarkyError::ZeroScalar)?);
+        Ok(Self::create_unchecked(trp, &dinv, qap))
     }
 
-    fn create_u(trp: &Trapdoor, qap: &ConstraintSystem) -> U {
-        let (a, b, _, x) = trp.extract();
-        let (_, n, _) = qap.shape();
-
-        let G = genG1!();
-        let H = genG2!();
-
-        let c1 = (0..2 * n - 1)
-            .into_par_iter()
-            .map(|i| {
-                let res = (
-                    smul1!(pow!(x, i), G),
-                    smul2!(pow!(x, i), H),
-                );
-                res
-            })
-            .collect();
-
-        let c2 = (0..n)
-            .into_par_iter()
-            .map(|i| {
-                let res = (
-                    smul1!(a * pow!(x, i), G),
-                    smul1!(b * pow!(x, i), G),
-                    smul2!(a * pow!(x, i), H),
-                    smul2!(b * pow!(x, i), H),
-                );
-                res
-            })
-            .collect();
-
-        (c1, c2)
+    // dinv must be the inverse of the d component of the trapdoor
+    fn create_unchecked(trp: &Trapdoor, dinv: &Scalar, qap: &ConstraintSystem) -> Self {
+        Self {
+            u: UniversalSRS::create_u(&trp, qap.shape().1),
+            s: Self::create_s(&trp, dinv, &qap),
+        }
     }
 
-    fn create_s(trp: &Trapdoor, qap: &ConstraintSystem) -> S {
-        let (a, b, d, x) = trp.extract();
+    fn create_s(trp: &Trapdoor, dinv: &Scalar, qap: &ConstraintSystem) -> S {
+        let Trapdoor(a, b, d, x) = trp;
         let (m, n, l) = qap.shape();
         let (u, v, w, t) = qap.collections();
 
         let G = genG1!();
         let H = genG2!();
 
-        let dinv = inv!(d);
+        let table = FixedBase::new(&G, m - l + n - 1);
 
-        let c1 = smul1!(d, G);
-        let c2 = smul2!(d, H);
+        let c1 = smul1!(*d, G);
+        let c2 = smul2!(*d, H);
 
-        let c3 = (l + 1..m + 1)
+        let mut scalars = (l + 1..m + 1)
             .into_par_iter()
             .map(|i| {
-                let ux_i = u[i].evaluate(&x);
-                let vx_i = v[i].evaluate(&x);
-                let wx_i = w[i].evaluate(&x);
-                smul1!((b * ux_i + a * vx_i + wx_i) * dinv, G)
+                let ux_i = u[i].evaluate(x);
+                let vx_i = v[i].evaluate(x);
+                let wx_i = w[i].evaluate(x);
+                (*b * ux_i + *a * vx_i + wx_i) * *dinv
             })
-            .collect();
-
-        let tx = t.evaluate(&x);
-        let c4 = (0..n - 1)
-            .into_par_iter()
-            .map(|i| smul1!(pow!(x, i) * tx * dinv, G))
-            .collect();
-
+            .collect::<Vec<Scalar>>();
+        let c3 = table.batch_mul(&scalars);
+        scalars.zeroize();
+
+        let mut tx = t.evaluate(x);
+        let mut scalars = powers(*x, n - 1)
+            .into_iter()
+            .map(|p| p * tx * *dinv)
+            .collect::<Vec<Scalar>>();
+        let c4 = table.batch_mul(&scalars);
+        scalars.zeroize();
+
+        tx.zeroize();
         (c1, c2, c3, c4)
     }
 
-    pub fn update(&mut self, qap: &ConstraintSystem, w: Witness) {
+    // Consumes the witness, which is zeroized on return. Fails if the SRS
+    // does not match the QAP or the phase 2 witness is zero. Phase 1 runs on
+    // the UniversalSRS before specialization, so that phase 1 witnesses are
+    // rejected with PhaseMismatch.
+    pub fn update(&mut self, qap: &ConstraintSystem, w: Witness) -> Result<(), SnarkyError> {
         let (m, n, l) = qap.shape();
-        match w {
-            Witness::ONE(a, b, x) => {
-                let srs_u = &self.u;
-
-                // phase 1, step 8 (recompute u-component)
-                let c1 = (0..2 * n - 1)
-                    .into_par_iter()
-                    .map(|i| {
-                        let res = (
-                            smul1!(pow!(x, i), srs_u.0[i].0),
-                            smul2!(pow!(x, i), srs_u.0[i].1),
-                        );
-                        res
-                    })
-                    .collect();
-                let c2 = (0..n)
-                    .into_par_iter()
-                    .map(|i| {
-                        let res = (
-                            smul1!(a * pow!(x, i), srs_u.1[i].0),
-                            smul1!(b * pow!(x, i), srs_u.1[i].1),
-                            smul2!(a * pow!(x, i), srs_u.1[i].2),
-                            smul2!(b * pow!(x, i), srs_u.1[i].3),
-                        );
-                        res
-                    }) 
-                    .collect();
-                let u_new: U =  (c1, c2);
-
-                // phase 1, step 9 (recompute s-component)
-                let s_new = SRS::specialize(&qap, &u_new);
-
-                // phase 1, step 10
-                self.u = u_new;
-                self.s = s_new;
-            },
+        self.check_shape(qap)?;
+        match &w {
+            Witness::ONE(..) => return Err(ProofError::PhaseMismatch.into()),
             Witness::TWO(d) => {
                 let srs_s = &self.s;
 
                 // phase 2, step 5  (recompute s-component)
-                let dinv = inv!(d);
-                let c1 = smul1!(d, srs_s.0);
-                let c2 = smul2!(d, srs_s.1);
+                let mut dinv = d.inverse().ok_or(SnarkyError::ZeroScalar)?;
+                let c1 = smul1!(*d, srs_s.0);
+                let c2 = smul2!(*d, srs_s.1);
                 let c3 = (0..m - l)
                     .into_par_iter()
                     .map(|i| smul1!(dinv, srs_s.2[i]))
@@ -201,87 +197,79 @@ impl SRS {
                     .into_par_iter()
                     .map(|i| smul1!(dinv, srs_s.3[i]))
                     .collect();
+                dinv.zeroize();
                 self.s = (c1, c2, c3, c4)
             },
         }
+        Ok(())
     }
 
-    fn specialize(qap: &ConstraintSystem, srs_u: &U) -> S {
+    // s-component of the SRS specialized to the QAP from the u-component,
+    // i.e., with unit d
+    pub(crate) fn specialize(qap: &ConstraintSystem, srs_u: &U) -> S {
         let (m, n, l) = qap.shape();
         let (u, v, w, t) = qap.collections();
-        let zero = zeroG1!();
 
         let c1 = genG1!();
         let c2 = genG2!();
-        let c3 = (0..m - l)
+        let bases = (0..n)
+            .flat_map(|j| vec![srs_u.1[j].1, srs_u.1[j].0, srs_u.0[j].0])
+            .collect::<Vec<G1>>();
+        let c3 = (l + 1..m + 1)
             .into_par_iter()
             .map(|i| {
-                (0..n)
-                    .into_par_iter()
-                    .map(|j| add1!(
-                        smul1!(u[i].coeff(j), srs_u.1[j].1),
-                        smul1!(v[i].coeff(j), srs_u.1[j].0),
-                        smul1!(w[i].coeff(j), srs_u.0[j].0)
-                    ))
-                    .reduce(|| zero, |acc, inc| add1!(acc, inc))
+                let scalars = (0..n)
+                    .flat_map(|j| vec![u[i].coeff(j), v[i].coeff(j), w[i].coeff(j)])
+                    .collect::<Vec<Scalar>>();
+                msm1!(bases, scalars)
             })
             .collect();
+        let powers = srs_u.0.iter().map(|p| p.0).collect::<Vec<G1>>();
+        let coeffs = (0..n + 1).map(|j| t.coeff(j)).collect::<Vec<Scalar>>();
         let c4 = (0..n - 1)
             .into_par_iter()
-            .map(|i| {
-                (0..n)
-                    .into_par_iter()
-                    .map(|j| smul1!(t.coeff(j), srs_u.0[i + j].0))
-                    .reduce(|| zero, |acc, inc| add1!(acc, inc))
-            })
+            .map(|i| msm1!(powers[i..i + n + 1], coeffs))
             .collect();
 
         (c1, c2, c3, c4)
     }
 
+    // Fails if the component lengths are not those determined by the QAP
+    pub fn check_shape(&self, qap: &ConstraintSystem) -> Result<(), SnarkyError> {
+        let (srs_u, srs_s) = (&self.u, &self.s);
+        match [srs_u.0.len(), srs_u.1.len(), srs_s.2.len(), srs_s.3.len()] == Self::lengths(qap.shape()) {
+            false   => Err(ShapeError::SrsMismatch.into()),
+            _       => Ok(())
+        }
+    }
+
     // verification: step 2
-    pub fn check_u(&self, qap: &ConstraintSystem) -> Result<bool, SRSError> {
+    pub fn check_u(&self, qap: &ConstraintSystem) -> Result<bool, SnarkyError> {
         let (_, n, _) = qap.shape();
         let srs_u = &self.u;
-        
-        let out1 = {
-            (srs_u.0.len() == 2 * n - 1) & 
-            (srs_u.1.len() == n)
-        };
-
-        let out2 = (0..2 * n - 1)
-            .into_par_iter()
-            .map(|i| {
-                contained_in_group!(srs_u.0[i].0) &
-                contained_in_group!(srs_u.0[i].1)
-            })
-            .reduce(|| true, |acc, b| acc & b);
-
-        let out3 = (0..n)
-            .into_par_iter()
-            .map(|i| {
-                contained_in_group!(srs_u.0[i].0) &
-                contained_in_group!(srs_u.0[i].1)
-            })
-            .reduce(|| true, |acc, b| acc & b);
 
+        if srs_u.0.len() != 2 * n - 1 || srs_u.1.len() != n {
+            return Err(ShapeError::SrsMismatch.into());
+        }
 
-        match out1 & out2 & out3 {
-            false   => Err(SRSError),
+        match u_contained_in_group(srs_u) {
+            false   => Err(SnarkyError::MalformedPoint),
             _       => Ok(true)
         }
     }
 
     // verification: step 7
-    pub fn check_s(&self, qap: &ConstraintSystem) -> Result<bool, SRSError> {
+    pub fn check_s(&self, qap: &ConstraintSystem) -> Result<bool, SnarkyError> {
         let (m, n, l) = qap.shape();
         let srs_s = &self.s;
-        
+
+        if srs_s.2.len() != m - l || srs_s.3.len() != n - 1 {
+            return Err(ShapeError::SrsMismatch.into());
+        }
+
         let out1 = {
             contained_in_group!(srs_s.0) &
-            contained_in_group!(srs_s.1) &
-            (srs_s.2.len() == m - l) &
-            (srs_s.3.len() == n - 1)
+            contained_in_group!(srs_s.1)
         };
 
         let out2 = (0..m - l)
@@ -295,14 +283,321 @@ impl SRS {
             .reduce(|| true, |acc, b| acc & b);
 
         match out1 & out2 & out3 {
-            false   => Err(SRSError),
+            false   => Err(SnarkyError::MalformedPoint),
             _       => Ok(true)
         }
     }
+
+    // Lengths of the SRS components as determined by the QAP shape
+    fn lengths(shape: (usize, usize, usize)) -> [usize; 4] {
+        let (m, n, l) = shape;
+        [2 * n - 1, n, m - l, n - 1]
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W, qap: &ConstraintSystem, encoding: Encoding)
+        -> Result<(), SerializationError>
+    {
+        let (m, n, l) = qap.shape();
+        let lengths = Self::lengths((m, n, l));
+        let (srs_u, srs_s) = (&self.u, &self.s);
+        if [srs_u.0.len(), srs_u.1.len(), srs_s.2.len(), srs_s.3.len()] != lengths {
+            return Err(SerializationError::ShapeMismatch);
+        }
+
+        // header
+        write_preamble(writer, &SRS_MAGIC, encoding)?;
+        for &val in [m, n, l].iter().chain(lengths.iter()) {
+            write_u64(writer, val as u64)?;
+        }
+
+        // u-component
+        write_u(writer, srs_u, encoding)?;
+
+        // s-component
+        write_g1(writer, &srs_s.0, encoding)?;
+        write_g2(writer, &srs_s.1, encoding)?;
+        for elem in srs_s.2.iter().chain(srs_s.3.iter()) {
+            write_g1(writer, elem, encoding)?;
+        }
+        Ok(())
+    }
+
+    // Loads the SRS after validating its header against the provided QAP
+    pub fn read<R: Read>(reader: &mut R, qap: &ConstraintSystem)
+        -> Result<Self, SerializationError>
+    {
+        let header = SRSHeader::read(reader)?;
+        header.validate(qap)?;
+        let encoding = header.encoding;
+        let [len1, len2, len3, len4] = Self::lengths(header.shape);
+
+        // u-component
+        let mut c1 = Vec::with_capacity(len1);
+        for _ in 0..len1 {
+            c1.push((
+                read_g1(reader, encoding)?,
+                read_g2(reader, encoding)?,
+            ));
+        }
+        let mut c2 = Vec::with_capacity(len2);
+        for _ in 0..len2 {
+            c2.push((
+                read_g1(reader, encoding)?,
+                read_g1(reader, encoding)?,
+                read_g2(reader, encoding)?,
+                read_g2(reader, encoding)?,
+            ));
+        }
+
+        // s-component
+        let c3 = read_g1(reader, encoding)?;
+        let c4 = read_g2(reader, encoding)?;
+        let mut c5 = Vec::with_capacity(len3);
+        for _ in 0..len3 {
+            c5.push(read_g1(reader, encoding)?);
+        }
+        let mut c6 = Vec::with_capacity(len4);
+        for _ in 0..len4 {
+            c6.push(read_g1(reader, encoding)?);
+        }
+
+        Ok(Self {
+            u: (c1, c2),
+            s: (c3, c4, c5, c6),
+        })
+    }
 }
 
 
-// Indicates SRS checking failure
+// Powers of x, followed by those of a, b
+pub(crate) fn write_u<W: Write>(writer: &mut W, srs_u: &U, encoding: Encoding)
+    -> Result<(), SerializationError>
+{
+    for (g1, g2) in srs_u.0.iter() {
+        write_g1(writer, g1, encoding)?;
+        write_g2(writer, g2, encoding)?;
+    }
+    for (a1, b1, a2, b2) in srs_u.1.iter() {
+        write_g1(writer, a1, encoding)?;
+        write_g1(writer, b1, encoding)?;
+        write_g2(writer, a2, encoding)?;
+        write_g2(writer, b2, encoding)?;
+    }
+    Ok(())
+}
+
+
+// Header of serialized SRS; can be read in advance in order
+// to check compatibility with a QAP before loading the SRS
 #[derive(Debug, PartialEq)]
-pub struct SRSError;
+pub struct SRSHeader {
+    pub version: u8,
+    pub encoding: Encoding,
+    pub shape: (usize, usize, usize),
+}
+
+impl SRSHeader {
+
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, SerializationError> {
+        let (version, encoding) = read_preamble(reader, &SRS_MAGIC)?;
+        let mut vals = [0usize; 7];
+        for val in vals.iter_mut() {
+            *val = read_u64(reader)? as usize;
+        }
+        let (m, n, l) = (vals[0], vals[1], vals[2]);
+        if n == 0 || n > usize::MAX / 2 || l >= m || vals[3..] != SRS::lengths((m, n, l)) {
+            return Err(SerializationError::ShapeMismatch);
+        }
+        Ok(Self { version, encoding, shape: (m, n, l) })
+    }
+
+    pub fn validate(&self, qap: &ConstraintSystem) -> Result<(), SerializationError> {
+        match self.shape == qap.shape() {
+            false   => Err(SerializationError::ShapeMismatch),
+            _       => Ok(())
+        }
+    }
+}
+
+
+// Group inclusion of all elements of the u-component
+fn u_contained_in_group(srs_u: &U) -> bool {
+    let out1 = srs_u.0
+        .par_iter()
+        .map(|p| contained_in_group!(p.0) & contained_in_group!(p.1))
+        .reduce(|| true, |acc, b| acc & b);
+
+    let out2 = srs_u.1
+        .par_iter()
+        .map(|p| {
+            contained_in_group!(p.0) &
+            contained_in_group!(p.1) &
+            contained_in_group!(p.2) &
+            contained_in_group!(p.3)
+        })
+        .reduce(|| true, |acc, b| acc & b);
+
+    out1 & out2
+}
+
+// Phase 1 parameters, i.e., the u-component of the SRS, independent of any
+// QAP. Supports all QAPs of degree up to n, to which it is specialized once
+// phase 1 is complete, so that phase 1 updates avoid the O(mn) specialization.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UniversalSRS {
+    pub u: U,
+    // Set upon import, where a, b are public (unit) until the first phase 1
+    // update; specialization is refused while set
+    pub(crate) pending_update: bool,
+}
+
+impl UniversalSRS {
+
+    pub fn setup_with_unit_trapdoor(n: usize) -> Result<(Self, Trapdoor), SnarkyError> {
+        let trp = Trapdoor::from_units();
+        Ok((Self::create(&trp, n)?, trp))
+    }
+
+    pub fn setup_with_random_trapdoor<R: SnarkyRng>(n: usize, rng: &mut R)
+        -> Result<(Self, Trapdoor), SnarkyError>
+    {
+        let trp = Trapdoor::random(rng);
+        Ok((Self::create(&trp, n)?, trp))
+    }
+
+    // Ignores the d component of the trapdoor. Fails if n is zero
+    pub fn create(trp: &Trapdoor, n: usize) -> Result<Self, SnarkyError> {
+        match n {
+            0 => return Err(ShapeError::EmptyCircuit.into()),
+            1 => return Err(ShapeError::TooFewConstraints.into()),
+            _ => (),
+        }
+        Ok(Self { u: Self::create_u(trp, n), pending_update: false })
+    }
+
+    pub(crate) fn create_u(trp: &Trapdoor, n: usize) -> U {
+        let Trapdoor(a, b, _, x) = trp;
+
+        let G = genG1!();
+        let H = genG2!();
+
+        let mut xs = powers(*x, 2 * n - 1);
+        let mut axs = xs[..n].iter().map(|&p| *a * p).collect::<Vec<Scalar>>();
+        let mut bxs = xs[..n].iter().map(|&p| *b * p).collect::<Vec<Scalar>>();
+        let table_1 = FixedBase::new(&G, 4 * n - 1);
+        let table_2 = FixedBase::new(&H, 4 * n - 1);
 
+        let c1 = table_1.batch_mul(&xs)
+            .into_iter()
+            .zip(table_2.batch_mul(&xs))
+            .collect();
+
+        let c2 = table_1.batch_mul(&axs)
+            .into_iter()
+            .zip(table_1.batch_mul(&bxs))
+            .zip(table_2.batch_mul(&axs))
+            .zip(table_2.batch_mul(&bxs))
+            .map(|(((ax_1, bx_1), ax_2), bx_2)| (ax_1, bx_1, ax_2, bx_2))
+            .collect();
+
+        xs.zeroize();
+        axs.zeroize();
+        bxs.zeroize();
+        (c1, c2)
+    }
+
+    // phase 1, step 8: u-component updated by the witness (a, b, x)
+    pub(crate) fn update_u(srs_u: &U, w: (&Scalar, &Scalar, &Scalar)) -> U {
+        let (a, b, x) = w;
+        let n = srs_u.1.len();
+        let mut xs = powers(*x, 2 * n - 1);
+        let c1 = (0..2 * n - 1)
+            .into_par_iter()
+            .map(|i| {
+                let res = (
+                    smul1!(xs[i], srs_u.0[i].0),
+                    smul2!(xs[i], srs_u.0[i].1),
+                );
+                res
+            })
+            .collect();
+        let c2 = (0..n)
+            .into_par_iter()
+            .map(|i| {
+                let (mut ax_i, mut bx_i) = (*a * xs[i], *b * xs[i]);
+                let res = (
+                    smul1!(ax_i, srs_u.1[i].0),
+                    smul1!(bx_i, srs_u.1[i].1),
+                    smul2!(ax_i, srs_u.1[i].2),
+                    smul2!(bx_i, srs_u.1[i].3),
+                );
+                ax_i.zeroize();
+                bx_i.zeroize();
+                res
+            }) 
+            .collect();
+        xs.zeroize();
+        (c1, c2)
+    }
+
+    // Maximum degree of the supported QAPs
+    pub fn degree(&self) -> usize {
+        self.u.1.len()
+    }
+
+    // Fails if the component lengths are inconsistent with each other or
+    // the degree is less than 2
+    pub fn check_shape(&self) -> Result<(), SnarkyError> {
+        let n = self.degree();
+        match n > 1 && self.u.0.len() == 2 * n - 1 {
+            false   => Err(ShapeError::SrsMismatch.into()),
+            _       => Ok(())
+        }
+    }
+
+    // Fails if the shape is invalid or some element is not in the group
+    pub fn check_u(&self) -> Result<bool, SnarkyError> {
+        self.check_shape()?;
+        match u_contained_in_group(&self.u) {
+            false   => Err(SnarkyError::MalformedPoint),
+            _       => Ok(true)
+        }
+    }
+
+    // Consumes the witness, which is zeroized on return. Fails upon phase 2
+    // witness or inconsistent lengths
+    pub fn update(&mut self, w: Witness) -> Result<(), SnarkyError> {
+        self.check_shape()?;
+        match &w {
+            Witness::ONE(a, b, x) => {
+                self.u = Self::update_u(&self.u, (a, b, x));
+                self.pending_update = false;
+                Ok(())
+            },
+            Witness::TWO(_) => Err(ProofError::PhaseMismatch.into()),
+        }
+    }
+
+    // Whether the parameters were imported and have not been updated since,
+    // so that a, b are still public
+    pub fn pending_update(&self) -> bool {
+        self.pending_update
+    }
+
+    // Transition to phase 2: SRS for the provided QAP, with the powers
+    // truncated to its degree. Fails if the QAP has larger degree, or with
+    // ImportError::NotUpdated if imported parameters have not been updated.
+    pub fn specialize(&self, qap: &ConstraintSystem) -> Result<SRS, SnarkyError> {
+        self.check_shape()?;
+        if self.pending_update {
+            return Err(ImportError::NotUpdated.into());
+        }
+        let (_, n, _) = qap.shape();
+        if n > self.degree() {
+            return Err(ShapeError::SrsMismatch.into());
+        }
+        let u = (self.u.0[..2 * n - 1].to_vec(), self.u.1[..n].to_vec());
+        let s = SRS::specialize(qap, &u);
+        Ok(SRS { u, s })
+    }
+}
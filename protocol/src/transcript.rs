@@ -0,0 +1,265 @@
+use crate::prover::{BatchProof, UpdateProof};
+use crate::serialize::*;
+use crate::srs::{SRS, UniversalSRS, write_u};
+use crate::updater::Phase;
+use circuits::ConstraintSystem;
+use util::SnarkyRng;
+pub use util::TranscriptError;
+use backend::*;
+
+use sha2::{Digest, Sha256};
+use ark_std::rand::Rng as ArkRng;
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write, Seek, SeekFrom};
+use std::path::Path;
+
+pub type Hash = [u8; 32];
+
+
+// SHA-256 over the compressed serialization of the SRS
+pub fn srs_digest(srs: &SRS, qap: &ConstraintSystem) -> Result<Hash, SerializationError> {
+    let mut buffer = Vec::<u8>::new();
+    srs.write(&mut buffer, qap, Encoding::COMPRESSED)?;
+    Ok(Sha256::digest(&buffer).into())
+}
+
+// SHA-256 over the degree and the compressed u-component of the universal SRS
+pub fn universal_digest(usrs: &UniversalSRS) -> Result<Hash, SerializationError> {
+    let mut buffer = Vec::<u8>::new();
+    write_u64(&mut buffer, usrs.degree() as u64)?;
+    write_u(&mut buffer, &usrs.u, Encoding::COMPRESSED)?;
+    Ok(Sha256::digest(&buffer).into())
+}
+
+
+// Record of a single contribution; commits to the hash of its previous one
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub index: u64,
+    pub proof: UpdateProof,
+    pub digest: Hash,           // Digest of the resulting (in phase 1, universal) SRS
+    pub prev: Hash,             // Hash of the previous entry
+}
+
+impl Entry {
+
+    pub fn phase(&self) -> Phase {
+        match self.proof {
+            UpdateProof::ONE(..) => Phase::ONE,
+            UpdateProof::TWO(..) => Phase::TWO,
+        }
+    }
+
+    // index | proof (leading with its phase) | digest | prev
+    fn write_content<W: Write>(&self, writer: &mut W, encoding: Encoding)
+        -> Result<(), SerializationError>
+    {
+        write_u64(writer, self.index)?;
+        self.proof.write(writer, encoding)?;
+        writer.write_all(&self.digest)?;
+        writer.write_all(&self.prev)?;
+        Ok(())
+    }
+
+    pub fn hash(&self, encoding: Encoding) -> Result<Hash, SerializationError> {
+        let mut buffer = Vec::<u8>::new();
+        self.write_content(&mut buffer, encoding)?;
+        Ok(Sha256::digest(&buffer).into())
+    }
+
+    // content | hash
+    pub fn write<W: Write>(&self, writer: &mut W, encoding: Encoding)
+        -> Result<Hash, SerializationError>
+    {
+        let mut buffer = Vec::<u8>::new();
+        self.write_content(&mut buffer, encoding)?;
+        let hash: Hash = Sha256::digest(&buffer).into();
+        writer.write_all(&buffer)?;
+        writer.write_all(&hash)?;
+        Ok(hash)
+    }
+
+    // Returns None at end of input; the stored hash is returned unchecked
+    pub fn read<R: Read>(reader: &mut R, encoding: Encoding)
+        -> Result<Option<(Self, Hash)>, SerializationError>
+    {
+        let mut buffer = [0u8; 8];
+        if reader.read(&mut buffer[..1])? == 0 {
+            return Ok(None);
+        }
+        reader.read_exact(&mut buffer[1..])?;
+        let index = u64::from_le_bytes(buffer);
+        let proof = UpdateProof::read(reader, encoding)?;
+        let mut digest = [0u8; 32];
+        reader.read_exact(&mut digest)?;
+        let mut prev = [0u8; 32];
+        reader.read_exact(&mut prev)?;
+        let mut hash = [0u8; 32];
+        reader.read_exact(&mut hash)?;
+        Ok(Some((Self { index, proof, digest, prev }, hash)))
+    }
+}
+
+
+// Append-only file of contributions, where each entry
+// commits to the hash of its previous one
+pub struct Transcript {
+    file: File,
+    encoding: Encoding,
+    last: Hash,
+    count: u64,
+}
+
+impl Transcript {
+
+    // Hash of the file header, used as predecessor of the first entry
+    fn genesis(encoding: Encoding) -> Result<Hash, SerializationError> {
+        let mut buffer = Vec::<u8>::new();
+        write_preamble(&mut buffer, &TRANSCRIPT_MAGIC, encoding)?;
+        Ok(Sha256::digest(&buffer).into())
+    }
+
+    // Fails if the file already exists
+    pub fn create<P: AsRef<Path>>(path: P, encoding: Encoding)
+        -> Result<Self, TranscriptError>
+    {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        write_preamble(&mut file, &TRANSCRIPT_MAGIC, encoding)?;
+        file.flush()?;
+        Ok(Self {
+            file,
+            encoding,
+            last: Self::genesis(encoding)?,
+            count: 0,
+        })
+    }
+
+    // Replays the hash chain of an existing file and prepares it for appending
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, TranscriptError> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(path)?;
+        let (encoding, entries) = Self::read_entries(&mut file)?;
+        let last = match entries.last() {
+            Some(entry) => entry.hash(encoding)?,
+            None        => Self::genesis(encoding)?,
+        };
+        file.seek(SeekFrom::End(0))?;
+        Ok(Self {
+            file,
+            encoding,
+            last,
+            count: entries.len() as u64,
+        })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    // Records a phase 2 contribution; srs must be the SRS resulting from the
+    // update
+    pub fn append(&mut self, qap: &ConstraintSystem, srs: &SRS, proof: &UpdateProof)
+        -> Result<Hash, TranscriptError>
+    {
+        self.append_entry(proof, srs_digest(srs, qap)?)
+    }
+
+    // Records a phase 1 contribution; usrs must be the universal SRS resulting
+    // from the update
+    pub fn append_universal(&mut self, usrs: &UniversalSRS, proof: &UpdateProof)
+        -> Result<Hash, TranscriptError>
+    {
+        self.append_entry(proof, universal_digest(usrs)?)
+    }
+
+    fn append_entry(&mut self, proof: &UpdateProof, digest: Hash) -> Result<Hash, TranscriptError> {
+        let entry = Entry {
+            index: self.count,
+            proof: proof.clone(),
+            digest,
+            prev: self.last,
+        };
+        let mut buffer = Vec::<u8>::new();
+        let hash = entry.write(&mut buffer, self.encoding)?;
+        self.file.write_all(&buffer)?;
+        self.file.flush()?;
+        self.last = hash;
+        self.count += 1;
+        Ok(hash)
+    }
+
+    // Reads all entries while checking their hash chain, indices and phase order
+    pub fn read_entries<R: Read>(reader: &mut R)
+        -> Result<(Encoding, Vec<Entry>), TranscriptError>
+    {
+        let (_, encoding) = read_preamble(reader, &TRANSCRIPT_MAGIC)?;
+        let mut entries = Vec::<Entry>::new();
+        let mut last = Self::genesis(encoding)?;
+        let mut phase_two = false;
+        while let Some((entry, hash)) = Entry::read(reader, encoding)? {
+            let index = entries.len() as u64;
+            if entry.index != index {
+                return Err(TranscriptError::InvalidIndex(entry.index));
+            }
+            match entry.phase() {
+                Phase::ONE if phase_two => {
+                    return Err(TranscriptError::InvalidPhase(index));
+                },
+                Phase::TWO => phase_two = true,
+                _ => (),
+            }
+            if entry.prev != last || entry.hash(encoding)? != hash {
+                return Err(TranscriptError::BrokenChain(index));
+            }
+            last = hash;
+            entries.push(entry);
+        }
+        Ok((encoding, entries))
+    }
+
+    // Replays the transcript against the final SRS and returns the
+    // reconstructed batch of update-proofs. The digest of the final SRS is
+    // checked if the last entry is of phase 2; otherwise, the SRS is bound to
+    // the last phase 1 update-proof by the batch verification.
+    pub fn verify<R: Read, G: SnarkyRng>(
+        reader: &mut R,
+        qap: &ConstraintSystem,
+        srs: &SRS,
+        rng: &mut G,
+    ) -> Result<BatchProof, TranscriptError>
+    {
+        let (_, entries) = Self::read_entries(reader)?;
+        if let Some(entry) = entries.last().filter(|entry| entry.phase() == Phase::TWO) {
+            if entry.digest != srs_digest(srs, qap)? {
+                return Err(TranscriptError::DigestMismatch);
+            }
+        }
+
+        let mut batch = BatchProof::initiate();
+        for entry in entries.into_iter() {
+            batch.append(entry.proof);
+        }
+
+        let len = std::cmp::max(batch.batch_1.len(), batch.batch_2.len());
+        let s = (0..len + 1)
+            .map(|_| rscalar!(rng))
+            .collect::<Vec::<Scalar>>();
+        let out =
+            batch.verify(srs, &s, Phase::ONE).unwrap_or(false) &
+            batch.verify(srs, &s, Phase::TWO).unwrap_or(false);
+        match out {
+            false   => Err(TranscriptError::BatchFailure),
+            _       => Ok(batch)
+        }
+    }
+}
@@ -1,10 +1,13 @@
-use crate::prover::{RhoProof, Witness, UpdateProof};
-use crate::srs::{Trapdoor, SRS};
+use crate::prover::{Witness, UpdateProof};
+use crate::srs::{SRS, UniversalSRS};
 use circuits::ConstraintSystem;
+use util::{SnarkyRng, SnarkyError};
 use backend::*;
 pub use crate::prover::BatchProof;
 
 use ark_std::rand::Rng as ArkRng;
+use num_traits::identities::Zero;
+use zeroize::Zeroizing;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Phase {
@@ -12,32 +15,70 @@ pub enum Phase {
     TWO = 2,
 }
 
-pub fn update(qap: &ConstraintSystem, srs: &mut SRS, batch: &mut BatchProof, phase: Phase) {
-
-    // phase 1/2: step 2
-    let witness = match phase {
-        Phase::ONE => {
-            Witness::ONE(
-                rscalar!(::util::snarky_rng()),
-                rscalar!(::util::snarky_rng()),
-                rscalar!(::util::snarky_rng()),
-            )
-        },
-        Phase::TWO => {
-            Witness::TWO(rscalar!(
-                ::util::snarky_rng()
-            ))
-        }
-    };
+// Phase 2 update of the SRS, returning the appended update-proof so that it
+// can also be recorded elsewhere. Fails, leaving SRS and batch untouched, if
+// the SRS does not match the QAP. Phase 1 runs on the UniversalSRS before
+// specialization (cf. update_universal).
+pub fn update<R: SnarkyRng>(
+    qap: &ConstraintSystem,
+    srs: &mut SRS,
+    batch: &mut BatchProof,
+    rng: &mut R,
+) -> Result<UpdateProof, SnarkyError>
+{
+    srs.check_shape(qap)?;
+
+    // phase 2: step 2
+    let witness = Witness::TWO(*nonzero_scalar(rng));
 
-    // phase 1: steps 3-7; phase 2: steps 3-4
-    batch.append(UpdateProof::create(
+    // phase 2: steps 3-4
+    let proof = UpdateProof::create(
         &srs, 
         &witness
-    ));
+    )?;
+
+    // phase 2: step 5
+    srs.update(&qap, witness)?;
+
+    batch.append(proof.clone());
+    Ok(proof)
+}
+
+// Phase 1 update of the universal SRS, appending to the batch the update-proof,
+// which is verified along with those of phase 2 after specialization
+pub fn update_universal<R: SnarkyRng>(
+    usrs: &mut UniversalSRS,
+    batch: &mut BatchProof,
+    rng: &mut R,
+) -> Result<UpdateProof, SnarkyError>
+{
+    usrs.check_shape()?;
 
-    // phase 1: steps 8-10; phase 2: step 5
-    srs.update(&qap, witness);
+    // phase 1: step 2
+    let (a, b, x) = (
+        Zeroizing::new(rscalar!(rng)),
+        Zeroizing::new(rscalar!(rng)),
+        Zeroizing::new(rscalar!(rng)),
+    );
+
+    // phase 1: steps 3-7
+    let proof = UpdateProof::create_one(&usrs.u, (&a, &b, &x))?;
+
+    // phase 1: step 8
+    usrs.u = UniversalSRS::update_u(&usrs.u, (&a, &b, &x));
+    usrs.pending_update = false;
+
+    batch.append(proof.clone());
+    Ok(proof)
+}
+
+fn nonzero_scalar<R: SnarkyRng>(rng: &mut R) -> Zeroizing<Scalar> {
+    loop {
+        let elem = Zeroizing::new(rscalar!(rng));
+        if !elem.is_zero() {
+            return elem;
+        }
+    }
 }
 
 
@@ -1,9 +1,13 @@
-pub use crate::srs::{Trapdoor, SRS};
+pub use crate::srs::{Trapdoor, SRS, UniversalSRS};
+use crate::srs::U;
 pub use crate::prover::BatchProof;
+use crate::prover::{UpdateProof, RhoProof};
 use circuits::ConstraintSystem;
+use util::{SnarkyRng, SnarkyError, ShapeError, ProofError};
 use crate::updater::Phase;
 
 use backend::*;
+use backend::multi_pairing::{Equation, multi_pairing_eq, multi_pairing_eq_batched};
 use ark_ec::{AffineCurve, PairingEngine};
 use num_traits::identities::Zero;
 use ark_std::rand::Rng as ArkRng;
@@ -37,8 +41,73 @@ impl From<bool> for Verification {
     }
 }
 
+// Verification steps, in the order they are checked (cf. verify)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum VerificationStep {
+    SrsU,                       // step 2: phase 1 SRS well-formed
+    UpdateProofsOne,            // step 4-6: phase 1 update-proofs
+    PowersOfX,                  // step 7: powers of x consistent
+    PowersOfAB,                 // step 8: alpha, beta components consistent
+    SrsS,                       // step 9: phase 2 SRS well-formed
+    UpdateProofsTwo,            // step 10-12: phase 2 update-proofs
+    SComponent,                 // step 13: s component consistent
+    TComponent,                 // step 14: t component consistent
+}
+
+impl VerificationStep {
+    pub const ALL: [VerificationStep; 8] = [
+        VerificationStep::SrsU,
+        VerificationStep::UpdateProofsOne,
+        VerificationStep::PowersOfX,
+        VerificationStep::PowersOfAB,
+        VerificationStep::SrsS,
+        VerificationStep::UpdateProofsTwo,
+        VerificationStep::SComponent,
+        VerificationStep::TComponent,
+    ];
+}
+
+// Outcome of a verification step; for failing update-proof steps, index
+// is that of the first offending contribution within its phase
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StepOutcome {
+    pub step: VerificationStep,
+    pub passed: bool,
+    pub index: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VerificationReport {
+    pub steps: Vec<StepOutcome>,
+}
+
+impl VerificationReport {
+
+    pub fn passed(&self) -> bool {
+        self.steps.iter().fold(true, |acc, outcome| acc & outcome.passed)
+    }
+
+    pub fn first_failure(&self) -> Option<&StepOutcome> {
+        self.steps.iter().find(|outcome| !outcome.passed)
+    }
+
+    pub fn failures(&self) -> Vec<&StepOutcome> {
+        self.steps.iter().filter(|outcome| !outcome.passed).collect()
+    }
+}
+
+impl From<&VerificationReport> for Verification {
+    #[inline]
+    fn from(source: &VerificationReport) -> Verification {
+        Verification::from(source.passed())
+    }
+}
+
 // No batching; use only for testing
 pub fn verify_naive(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof) -> Verification {
+    if srs.check_shape(qap).is_err() {
+        return Verification::FAILURE;
+    }
     let (m, n, l) = qap.shape();
     let (u, v, w, t) = qap.collections();
     let G = genG1!();
@@ -85,12 +154,13 @@ pub fn verify_naive(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof) -> Ve
     let out_g = (0..m - l)
         .into_par_iter()
         .map(|i| {
+            let k = l + 1 + i;
             let s_i = (0..n)
                 .into_par_iter()
                 .map(|j| add1!(
-                    smul1!(u[i].coeff(j), srs_u.1[j].1),
-                    smul1!(v[i].coeff(j), srs_u.1[j].0),
-                    smul1!(w[i].coeff(j), srs_u.0[j].0)
+                    smul1!(u[k].coeff(j), srs_u.1[j].1),
+                    smul1!(v[k].coeff(j), srs_u.1[j].0),
+                    smul1!(w[k].coeff(j), srs_u.0[j].0)
                 ))
                 .reduce(|| zeroG1!(), |acc, inc| add1!(acc, inc));
             ct_eq!(pair!(srs_s.2[i], srs_s.1), pair!(s_i, H))
@@ -99,7 +169,7 @@ pub fn verify_naive(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof) -> Ve
 
     // step 11
     let out_h = {
-        let Gt = (0..n - 1)
+        let Gt = (0..n + 1)
             .into_par_iter()
             .map(|j| smul1!(t.coeff(j), srs_u.0[j].0))
             .reduce(|| zeroG1!(), |acc, inc| add1!(acc, inc));
@@ -125,10 +195,78 @@ pub fn verify_naive(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof) -> Ve
 }
 
 
-pub fn verify(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof) -> Verification {
+// Outcome of the checks of a verification step that involve no pairings,
+// along with the pairing equations of the step
+struct Step {
+    out: bool,
+    equations: Vec<Equation>,
+}
+
+impl Step {
+
+    fn from_checks(out: bool) -> Self {
+        Self { out, equations: Vec::new() }
+    }
+
+    fn from_equations(equations: Vec<Equation>) -> Self {
+        Self { out: true, equations }
+    }
+
+    fn holds(&self) -> bool {
+        self.out & self.equations
+            .par_iter()
+            .map(|pairs| multi_pairing_eq(pairs))
+            .reduce(|| true, |acc, b| acc & b)
+    }
+}
+
+// verification: step 7, i.e., the u-component contains successive powers of
+// the same x. Assumes well-shaped u-component and at least 2n - 1 scalars
+fn powers_equations(srs_u: &U, s: &[Scalar]) -> Vec<Equation> {
+    let n = srs_u.1.len();
+    let (g, h) = (genG1!(), genG2!());
+    let powers_1 = srs_u.0.iter().map(|p| p.0).collect::<Vec<G1Elem>>();
+    let powers_2 = srs_u.0.iter().map(|p| p.1).collect::<Vec<G2Elem>>();
+    let A = msm1!(powers_1[1..2 * n - 1], s[1..2 * n - 1]);
+    let B = msm2!(powers_2[1..2 * n - 1], s[1..2 * n - 1]);
+    let C = msm1!(powers_1[..2 * n - 2], s[1..2 * n - 1]);
+    vec![
+        vec![(A, h), (-g, B)],
+        vec![(A, h), (-C, srs_u.0[1].1)],
+    ]
+}
+
+// verification: step 8, i.e., the alpha, beta components are the powers of x
+// multiplied by the same a, b. Assumes well-shaped u-component and at least n
+// scalars
+fn ab_equations(srs_u: &U, s: &[Scalar]) -> Vec<Equation> {
+    let n = srs_u.1.len();
+    let (g, h) = (genG1!(), genG2!());
+    let powers_1 = srs_u.0.iter().map(|p| p.0).collect::<Vec<G1Elem>>();
+    let (A, B, C, D, E) = (
+        msm1!(powers_1[..n], s[..n]),
+        msm1!(srs_u.1.iter().map(|p| p.0).collect::<Vec<G1Elem>>(), s[..n]),
+        msm1!(srs_u.1.iter().map(|p| p.1).collect::<Vec<G1Elem>>(), s[..n]),
+        msm2!(srs_u.1.iter().map(|p| p.2).collect::<Vec<G2Elem>>(), s[..n]),
+        msm2!(srs_u.1.iter().map(|p| p.3).collect::<Vec<G2Elem>>(), s[..n]),
+    );
+    vec![
+        vec![(B, h), (-g, D)],
+        vec![(B, h), (-A, srs_u.1[0].2)],
+        vec![(C, h), (-g, E)],
+        vec![(C, h), (-A, srs_u.1[0].3)],
+    ]
+}
+
+// All steps fail if the SRS does not match the QAP
+fn steps<R: SnarkyRng>(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof, rng: &mut R)
+    -> Vec<Step>
+{
+    if srs.check_shape(qap).is_err() {
+        return VerificationStep::ALL.iter().map(|_| Step::from_checks(false)).collect();
+    }
     let (m, n, l) = qap.shape();
     let (u, v, w, t) = qap.collections();
-    let G = genG1!();
     let H = genG2!();
 
     // step 1
@@ -136,131 +274,242 @@ pub fn verify(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof) -> Verifica
     let srs_s = &srs.s;
 
     // step 2
-    let out_a = srs.check_u(&qap).unwrap_or(false);
+    let out_a = Step::from_checks(srs.check_u(&qap).unwrap_or(false));
 
     // step 3
     let aux = [2 * n - 2, m, batch.batch_1.len(), batch.batch_2.len()];
     let max = *aux.iter().max().unwrap();
     let s = (0..max + 1)
-        .into_par_iter()
-        .map(|_| rscalar!(::util::snarky_rng()))
+        .map(|_| rscalar!(rng))
         .collect::<Vec::<backend::Scalar>>();
 
     // step 4-6
-    let out_b = batch.verify(&srs, &s, Phase::ONE).unwrap_or(false);
+    let (equations, out) = batch.equations(&srs, &s, Phase::ONE);
+    let out_b = Step { out, equations };
 
     // step 7
-    let (A, B, C) = (1..2 * n - 1)
-        .into_par_iter()
-        .map(|i| (
-            smul1!(s[i], srs_u.0[i].0),
-            smul2!(s[i], srs_u.0[i].1),
-            smul1!(s[i], srs_u.0[i -  1].0)
-        ))
-        .reduce(|| (zeroG1!(), zeroG2!(), zeroG1!()),
-            |
-                (a1, b1, c1), 
-                (a2, b2, c2)
-            | 
-            (
-                a1 + a2, 
-                b1 + b2, 
-                c1 + c2,
-            )
-        );
-    let out_c = ct_eq!(pair!(A, H), pair!(G, B)) & 
-                ct_eq!(pair!(A, H), pair!(C, srs_u.0[1].1));
-    
+    let out_c = Step::from_equations(powers_equations(srs_u, &s));
+
     // step 8
-    let (A, B, C, D, E) = (0..n)
-        .into_par_iter()
-        .map(|i| (
-            smul1!(s[i], srs_u.0[i].0),
-            smul1!(s[i], srs_u.1[i].0),
-            smul1!(s[i], srs_u.1[i].1),
-            smul2!(s[i], srs_u.1[i].2),
-            smul2!(s[i], srs_u.1[i].3),
-        ))
-        .reduce(|| (zeroG1!(), zeroG1!(), zeroG1!(), zeroG2!(), zeroG2!()),
-            |
-                (a1, b1, c1, d1, e1), 
-                (a2, b2, c2, d2, e2)
-            | 
-            (
-                a1 + a2, 
-                b1 + b2, 
-                c1 + c2, 
-                d1 + d2, 
-                e1 + e2,
-            )
-        );
-    let out_d = ct_eq!(pair!(B, H), pair!(G, D)) &
-                ct_eq!(pair!(B, H), pair!(A, srs_u.1[0].2)) &
-                ct_eq!(pair!(C, H), pair!(G, E)) &
-                ct_eq!(pair!(C, H), pair!(A, srs_u.1[0].3));
+    let out_d = Step::from_equations(ab_equations(srs_u, &s));
 
     // step 9
-    let out_e = srs.check_s(&qap).unwrap_or(false);
+    let out_e = Step::from_checks(srs.check_s(&qap).unwrap_or(false));
 
     // step 10-12
-    let out_f = batch.verify(&srs, &s, Phase::TWO).unwrap_or(false);
+    let (equations, out) = batch.equations(&srs, &s, Phase::TWO);
+    let out_f = Step { out, equations };
 
     // step 13
-    let (A, B) = (0..m - l)
+    let A = msm1!(srs_s.2, s[..m - l]);
+    let bases = (0..n)
+        .flat_map(|j| vec![srs_u.1[j].1, srs_u.1[j].0, srs_u.0[j].0])
+        .collect::<Vec<G1Elem>>();
+    let scalars = (0..n)
         .into_par_iter()
-        .map(|i| {
-            let sum = (0..n)
-                .into_par_iter()
-                .map(|j| {
-                    smul1!(u[i].coeff(j), srs_u.1[j].1) +
-                    smul1!(v[i].coeff(j), srs_u.1[j].0) +
-                    smul1!(w[i].coeff(j), srs_u.0[j].0)
+        .flat_map(|j| {
+            let (a, b, c) = (0..m - l)
+                .map(|i| {
+                    let k = l + 1 + i;
+                    (s[i] * u[k].coeff(j), s[i] * v[k].coeff(j), s[i] * w[k].coeff(j))
                 })
-                .reduce(|| zeroG1!(), |acc, inc| acc + inc);
-            (
-                smul1!(s[i], srs_s.2[i]),
-                smul1!(s[i], sum),
-            )
+                .fold((zero!(), zero!(), zero!()), |acc, inc| {
+                    (acc.0 + inc.0, acc.1 + inc.1, acc.2 + inc.2)
+                });
+            vec![a, b, c]
         })
-        .reduce(|| (zeroG1!(), zeroG1!()),
-            |
-                (a1, b1),
-                (a2, b2),
-            | 
-            (a1 + a2, b1 + b2)
-        );
-    let out_g = ct_eq!(pair!(A, srs_s.1), pair!(B, H));
+        .collect::<Vec<Scalar>>();
+    let B = msm1!(bases, scalars);
+    let out_g = Step::from_equations(vec![vec![(A, srs_s.1), (-B, H)]]);
 
     // step 14
-    let (Gt, A, B) = (0..n - 1)
-        .into_par_iter()
-        .map(|i| (
-            smul1!(t.coeff(i), srs_u.0[i].0),
-            smul1!(s[i], srs_s.3[i]),
-            smul2!(s[i], srs_u.0[i].1),
-        ))
-        .reduce(|| (zeroG1!(), zeroG1!(), zeroG2!()),
-            |
-                (a1, b1, c1), 
-                (a2, b2, c2)
-            | 
-            (
-                a1 + a2, 
-                b1 + b2, 
-                c1 + c2,
-            )
-        );
-    let out_h = ct_eq!(pair!(A, srs_s.1), pair!(Gt, B));
-    
+    let powers_1 = srs_u.0.iter().map(|p| p.0).collect::<Vec<G1Elem>>();
+    let powers_2 = srs_u.0.iter().map(|p| p.1).collect::<Vec<G2Elem>>();
+    let coeffs = (0..n + 1).map(|j| t.coeff(j)).collect::<Vec<Scalar>>();
+    let Gt = msm1!(powers_1[..n + 1], coeffs);
+    let A = msm1!(srs_s.3, s[..n - 1]);
+    let B = msm2!(powers_2[..n - 1], s[..n - 1]);
+    let out_h = Step::from_equations(vec![vec![(A, srs_s.1), (-Gt, B)]]);
 
-    Verification::from({
-        out_a & 
-        out_b & 
-        out_c & 
-        out_d & 
-        out_e & 
-        out_f & 
-        out_g & 
-        out_h
-    })
+    vec![out_a, out_b, out_c, out_d, out_e, out_f, out_g, out_h]
+}
+
+pub fn verify<R: SnarkyRng>(qap: &ConstraintSystem, srs: &SRS, batch: &BatchProof, rng: &mut R)
+    -> Verification
+{
+    Verification::from(
+        steps(qap, srs, batch, rng)
+            .iter()
+            .fold(true, |acc, step| acc & step.holds())
+    )
+}
+
+// Fully batched mode: decides all pairing equations of the above by a single
+// multi-pairing, after combining them with fresh random coefficients. Use
+// verify_detailed to locate the failing step upon failure.
+pub fn verify_batched<R: SnarkyRng>(
+    qap: &ConstraintSystem,
+    srs: &SRS,
+    batch: &BatchProof,
+    rng: &mut R,
+) -> Verification
+{
+    let steps = steps(qap, srs, batch, rng);
+    let out = steps.iter().fold(true, |acc, step| acc & step.out);
+    let equations = steps
+        .into_iter()
+        .flat_map(|step| step.equations)
+        .collect::<Vec<Equation>>();
+    let r = (0..equations.len())
+        .map(|_| rscalar!(rng))
+        .collect::<Vec::<backend::Scalar>>();
+    Verification::from(out & multi_pairing_eq_batched(&equations, &r))
+}
+
+// As verify, reporting the outcome of each step separately. Upon failure of
+// an update-proof step, the offending contribution is located by checking
+// each update-proof separately. Unlike verify, this is not constant-time
+// with respect to the location of failures; use only for diagnostics.
+pub fn verify_detailed<R: SnarkyRng>(
+    qap: &ConstraintSystem,
+    srs: &SRS,
+    batch: &BatchProof,
+    rng: &mut R,
+) -> VerificationReport
+{
+    let shape = srs.check_shape(qap).is_ok();
+    let steps = steps(qap, srs, batch, rng)
+        .iter()
+        .zip(VerificationStep::ALL.iter())
+        .map(|(step, &kind)| {
+            let passed = step.holds();
+            let index = match (passed || !shape, kind) {
+                (false, VerificationStep::UpdateProofsOne) => batch.locate_failure(srs, Phase::ONE),
+                (false, VerificationStep::UpdateProofsTwo) => batch.locate_failure(srs, Phase::TWO),
+                _ => None,
+            };
+            StepOutcome { step: kind, passed, index }
+        })
+        .collect();
+    VerificationReport { steps }
+}
+
+// Constant-time equality of u-components of equal lengths
+fn ct_eq_u(left: &U, right: &U) -> bool {
+    if left.0.len() != right.0.len() || left.1.len() != right.1.len() {
+        return false;
+    }
+    let eq_1 = left.0.iter().zip(right.0.iter())
+        .fold(true, |acc, (p, q)| acc & ct_eq!(p.0, q.0) & ct_eq!(p.1, q.1));
+    let eq_2 = left.1.iter().zip(right.1.iter())
+        .fold(true, |acc, (p, q)| {
+            acc & ct_eq!(p.0, q.0) & ct_eq!(p.1, q.1) & ct_eq!(p.2, q.2) & ct_eq!(p.3, q.3)
+        });
+    eq_1 & eq_2
+}
+
+// Rho-proof of a contribution against the SRS element preceding it, which it
+// must map to the (nonzero) element following it
+fn check_link(rho: &RhoProof, base: &G1Elem, next: &G1Elem) -> Result<(), SnarkyError> {
+    let gens = (genG1!(), genG2!());
+    rho.verify((&gens.0, &gens.1), None)?;
+    if !ct_eq!(pair!(rho.aux, gens.1), pair!(*base, rho.com.1)) {
+        return Err(ProofError::RhoFailure.into());
+    }
+    match ct_eq!(rho.aux, *next) & ct_ne!(*next, zeroG1!()) {
+        false   => Err(ProofError::UpdateFailure.into()),
+        _       => Ok(())
+    }
+}
+
+// Checks a phase 2 contribution as it arrives, i.e., the update-proof against
+// the SRS preceding it and the resulting SRS against both, so that a bad
+// contribution is rejected immediately. The previous SRS is assumed to have
+// been verified in the same way (or to be the specialization of a verified
+// universal SRS), so that only the elements changed by the update are checked.
+pub fn verify_update<R: SnarkyRng>(
+    qap: &ConstraintSystem,
+    prev: &SRS,
+    next: &SRS,
+    proof: &UpdateProof,
+    rng: &mut R,
+) -> Result<(), SnarkyError>
+{
+    prev.check_shape(qap)?;
+    next.check_shape(qap)?;
+    next.check_s(qap)?;
+    let rho = match proof {
+        UpdateProof::TWO(rho) => rho,
+        _ => return Err(ProofError::PhaseMismatch.into()),
+    };
+    check_link(rho, &prev.s.0, &next.s.0)?;
+
+    // [d]_1, [d]_2 consistent, and the s, t components divided by the same d
+    // as in the previous SRS, i.e., e(s_i', [d']_2) = e(s_i, [d]_2)
+    let (m, n, l) = qap.shape();
+    let r = (0..m - l + n - 1)
+        .map(|_| rscalar!(rng))
+        .collect::<Vec<Scalar>>();
+    let (acc_next, acc_prev) = (
+        add1!(msm1!(next.s.2, r[..m - l]), msm1!(next.s.3, r[m - l..])),
+        add1!(msm1!(prev.s.2, r[..m - l]), msm1!(prev.s.3, r[m - l..])),
+    );
+    let equations = vec![
+        vec![(next.s.0, genG2!()), (-genG1!(), next.s.1)],
+        vec![(acc_next, next.s.1), (-acc_prev, prev.s.1)],
+    ];
+    let scalars = [rscalar!(rng), rscalar!(rng)];
+
+    // u-component left intact
+    match ct_eq_u(&prev.u, &next.u) & multi_pairing_eq_batched(&equations, &scalars) {
+        false   => Err(ProofError::UpdateFailure.into()),
+        _       => Ok(())
+    }
+}
+
+// Checks a phase 1 contribution to the universal SRS as it arrives, similarly
+// to verify_update. Since the update changes the whole u-component, the latter
+// is checked for consistency in full (cf. verification steps 7-8).
+pub fn verify_update_universal<R: SnarkyRng>(
+    prev: &UniversalSRS,
+    next: &UniversalSRS,
+    proof: &UpdateProof,
+    rng: &mut R,
+) -> Result<(), SnarkyError>
+{
+    prev.check_shape()?;
+    next.check_shape()?;
+    if prev.degree() != next.degree() {
+        return Err(ShapeError::SrsMismatch.into());
+    }
+    next.check_u()?;
+    let (rho_a, rho_b, rho_x) = match proof {
+        UpdateProof::ONE(rho_a, rho_b, rho_x) => (rho_a, rho_b, rho_x),
+        _ => return Err(ProofError::PhaseMismatch.into()),
+    };
+
+    // [a]_1, [b]_1 and [x]_1 before and after the update
+    let (ab_prev, ab_next, x_prev, x_next) = match (
+        prev.u.1.first(), next.u.1.first(), prev.u.0.get(1), next.u.0.get(1)
+    ) {
+        (Some(p), Some(q), Some(x), Some(y)) => (p, q, x.0, y.0),
+        _ => return Err(ShapeError::SrsMismatch.into()),
+    };
+    check_link(rho_a, &ab_prev.0, &ab_next.0)?;
+    check_link(rho_b, &ab_prev.1, &ab_next.1)?;
+    check_link(rho_x, &x_prev, &x_next)?;
+
+    let n = next.degree();
+    let s = (0..2 * n - 1)
+        .map(|_| rscalar!(rng))
+        .collect::<Vec<Scalar>>();
+    let equations = [powers_equations(&next.u, &s), ab_equations(&next.u, &s)].concat();
+    let scalars = equations
+        .iter()
+        .map(|_| rscalar!(rng))
+        .collect::<Vec<Scalar>>();
+    match multi_pairing_eq_batched(&equations, &scalars) {
+        false   => Err(ProofError::UpdateFailure.into()),
+        _       => Ok(())
+    }
 }
@@ -1,4 +1,132 @@
 use protocol::prover::{BatchProof, ProofError};
-use util::map;
+use protocol::{SRS, UniversalSRS, Phase, Encoding, SerializationError};
+use circuits::ConstraintSystem;
+use util::SnarkyRng;
+use std::io::Cursor;
 
-// TODO: Write tests
+// Runs the provided numbers of phase 1 and phase 2 updates from a random
+// trapdoor, returning the final SRS along with the batch of update-proofs
+fn run_ceremony<R: SnarkyRng>(qap: &ConstraintSystem, nr_1: usize, nr_2: usize, rng: &mut R)
+    -> (SRS, BatchProof)
+{
+    let (_, n, _) = qap.shape();
+    let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(n, rng).unwrap();
+    let mut batch = BatchProof::initiate();
+    for _ in 0..nr_1 {
+        protocol::update_universal(&mut usrs, &mut batch, rng).unwrap();
+    }
+    let mut srs = usrs.specialize(qap).unwrap();
+    for _ in 0..nr_2 {
+        protocol::update(qap, &mut srs, &mut batch, rng).unwrap();
+    }
+    (srs, batch)
+}
+
+#[test]
+fn test_batch_serialization_roundtrip() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, batch) = run_ceremony(&qap, 3, 2, &mut rng);
+    for encoding in [Encoding::COMPRESSED, Encoding::UNCOMPRESSED].iter() {
+        let mut buffer = Vec::<u8>::new();
+        batch.write(&mut buffer, *encoding).unwrap();
+        let loaded = BatchProof::read(&mut Cursor::new(&buffer)).unwrap();
+        assert_eq!(loaded, batch);
+        assert!(bool::from(protocol::verify(&qap, &srs, &loaded, &mut rng)));
+    }
+}
+
+#[test]
+fn test_empty_batch_serialization() {
+    let batch = BatchProof::initiate();
+    let mut buffer = Vec::<u8>::new();
+    batch.write(&mut buffer, Encoding::COMPRESSED).unwrap();
+    assert_eq!(buffer.len(), 4 + 1 + 1 + 2 * 8);
+    assert_eq!(BatchProof::read(&mut Cursor::new(&buffer)).unwrap(), batch);
+}
+
+#[test]
+fn test_batch_malformed_input() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (_, batch) = run_ceremony(&qap, 1, 0, &mut rng);
+    let mut buffer = Vec::<u8>::new();
+    batch.write(&mut buffer, Encoding::COMPRESSED).unwrap();
+
+    let mut corrupted = buffer.clone();
+    corrupted[5] = 2;
+    assert_eq!(
+        BatchProof::read(&mut Cursor::new(&corrupted)).unwrap_err(),
+        SerializationError::InvalidEncoding(2)
+    );
+
+    let truncated = &buffer[..buffer.len() - 1];
+    assert_eq!(
+        BatchProof::read(&mut Cursor::new(truncated)).unwrap_err(),
+        SerializationError::IoFailure(std::io::ErrorKind::UnexpectedEof)
+    );
+}
+
+#[test]
+fn test_tampered_batch_fails_verification_after_loading() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, batch) = run_ceremony(&qap, 1, 1, &mut rng);
+    let mut buffer = Vec::<u8>::new();
+    batch.write(&mut buffer, Encoding::COMPRESSED).unwrap();
+    let mut loaded = BatchProof::read(&mut Cursor::new(&buffer)).unwrap();
+    loaded.batch_1[0][0].aux = loaded.batch_1[0][1].aux;
+    assert_eq!(
+        loaded.verify_naive(&srs, Phase::ONE).unwrap_err(),
+        ProofError::BatchFailure
+    );
+}
+
+#[test]
+fn test_batch_with_tampered_proof_of_knowledge() {
+    let mut rng = ::util::snarky_rng();
+    use backend::{genG1, add1, one};
+    use ark_ec::AffineCurve;
+    use num_traits::identities::{Zero, One};
+
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, mut batch) = run_ceremony(&qap, 2, 2, &mut rng);
+    let s = vec![one!(); 3];
+    assert_eq!(batch.verify(&srs, &s, Phase::ONE), Ok(true));
+    assert_eq!(batch.verify(&srs, &s, Phase::TWO), Ok(true));
+
+    batch.batch_1[1][0].prf = add1!(batch.batch_1[1][0].prf, genG1!());
+    assert_eq!(
+        batch.verify(&srs, &s, Phase::ONE).unwrap_err(),
+        ProofError::BatchFailure
+    );
+    batch.batch_2[1].prf = add1!(batch.batch_2[1].prf, genG1!());
+    assert_eq!(
+        batch.verify(&srs, &s, Phase::TWO).unwrap_err(),
+        ProofError::BatchFailure
+    );
+}
+
+#[test]
+fn test_batch_locates_failing_contribution() {
+    let mut rng = ::util::snarky_rng();
+    use backend::{genG1, add1, one};
+    use ark_ec::AffineCurve;
+    use num_traits::identities::{Zero, One};
+
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, mut batch) = run_ceremony(&qap, 3, 3, &mut rng);
+    let s = vec![one!(); 4];
+    for phase in [Phase::ONE, Phase::TWO].iter() {
+        assert!(batch.verify(&srs, &s, *phase).is_ok());
+        assert_eq!(batch.locate_failure(&srs, *phase), None);
+    }
+
+    batch.batch_1[1][2].prf = add1!(batch.batch_1[1][2].prf, genG1!());
+    assert!(batch.verify(&srs, &s, Phase::ONE).is_err());
+    assert_eq!(batch.locate_failure(&srs, Phase::ONE), Some(1));
+
+    batch.batch_2[2].aux = add1!(batch.batch_2[2].aux, genG1!());
+    assert!(batch.verify(&srs, &s, Phase::TWO).is_err());
+    assert_eq!(batch.locate_failure(&srs, Phase::TWO), Some(2));
+}
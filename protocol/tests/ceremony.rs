@@ -0,0 +1,114 @@
+use backend::*;
+use circuits::ConstraintSystem;
+use protocol::{Ceremony, Stage, CeremonyError, BatchProof, UniversalSRS};
+use protocol::{SnarkyError, ShapeError, ProofError};
+use num_traits::identities::Zero;
+use ark_ec::AffineCurve;
+
+#[test]
+fn test_ceremony_flow() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let mut ceremony = Ceremony::initialize(qap, &mut rng).unwrap();
+    assert_eq!(ceremony.stage(), Stage::PhaseOne);
+    assert_eq!(ceremony.srs(), None);
+
+    for _ in 0..2 {
+        ceremony.contribute(&mut rng).unwrap();
+    }
+    assert!(bool::from(ceremony.verify(&mut rng)));
+    ceremony.specialize().unwrap();
+    assert_eq!(ceremony.stage(), Stage::PhaseTwo);
+    for _ in 0..2 {
+        ceremony.contribute(&mut rng).unwrap();
+    }
+    assert!(bool::from(ceremony.verify(&mut rng)));
+    assert_eq!((ceremony.batch().batch_1.len(), ceremony.batch().batch_2.len()), (2, 2));
+
+    let srs = ceremony.finalize(&mut rng).unwrap().clone();
+    assert_eq!(ceremony.stage(), Stage::Finalized);
+    assert_eq!(ceremony.srs(), Some(&srs));
+    assert!(bool::from(protocol::verify(ceremony.qap(), &srs, ceremony.batch(), &mut rng)));
+}
+
+#[test]
+fn test_ceremony_rejects_illegal_transitions() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let mut ceremony = Ceremony::initialize(qap, &mut rng).unwrap();
+    let illegal = |err| Err(SnarkyError::Ceremony(err));
+
+    ceremony.contribute(&mut rng).unwrap();
+    assert_eq!(ceremony.finalize(&mut rng).map(|_| ()), illegal(CeremonyError::NotSpecialized));
+    assert_eq!(ceremony.stage(), Stage::PhaseOne);
+
+    ceremony.specialize().unwrap();
+    ceremony.contribute(&mut rng).unwrap();
+    assert_eq!(ceremony.specialize(), illegal(CeremonyError::AlreadySpecialized));
+    assert_eq!(ceremony.batch().batch_1.len(), 1);
+
+    ceremony.finalize(&mut rng).unwrap();
+    assert_eq!(ceremony.contribute(&mut rng).map(|_| ()), illegal(CeremonyError::Finalized));
+    assert_eq!(ceremony.specialize(), illegal(CeremonyError::Finalized));
+    assert_eq!(ceremony.finalize(&mut rng).map(|_| ()), illegal(CeremonyError::Finalized));
+    assert_eq!(ceremony.batch().batch_2.len(), 1);
+}
+
+#[test]
+fn test_ceremony_finalization_fails_upon_tampered_batch() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    let mut batch = BatchProof::initiate();
+    for _ in 0..3 {
+        protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+    }
+    let val = batch.batch_1[2][0].com.0;
+    batch.batch_1[2][0].com.0 = add1!(val, genG1!());
+
+    let mut ceremony = Ceremony::resume(qap, usrs, batch).unwrap();
+    assert!(!bool::from(ceremony.verify(&mut rng)));
+    ceremony.specialize().unwrap();
+    assert_eq!(
+        ceremony.finalize(&mut rng).map(|_| ()),
+        Err(SnarkyError::Proof(ProofError::BatchFailure))
+    );
+    assert_eq!(ceremony.stage(), Stage::PhaseTwo);
+}
+
+#[test]
+fn test_ceremony_resume_errors_upon_bad_input() {
+    let mut rng = ::util::snarky_rng();
+    let qap = || ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (usrs, _) = UniversalSRS::setup_with_random_trapdoor(3, &mut rng).unwrap();
+    assert_eq!(
+        Ceremony::resume(qap(), usrs, BatchProof::initiate()).unwrap_err(),
+        SnarkyError::Shape(ShapeError::SrsMismatch)
+    );
+
+    let (usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    let mut ceremony = Ceremony::initialize(qap(), &mut rng).unwrap();
+    ceremony.specialize().unwrap();
+    ceremony.contribute(&mut rng).unwrap();
+    let mut batch = BatchProof::initiate();
+    batch.append(ceremony.contribute(&mut rng).unwrap());
+    assert_eq!(
+        Ceremony::resume(qap(), usrs, batch).unwrap_err(),
+        SnarkyError::Proof(ProofError::PhaseMismatch)
+    );
+}
+
+#[test]
+fn test_ceremony_errors_upon_single_constraint() {
+    let mut rng = ::util::snarky_rng();
+    let qap = || {
+        let mut qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+        qap.n = 1;
+        qap
+    };
+    let degenerate = Err(SnarkyError::Ceremony(CeremonyError::TooFewConstraints));
+    assert_eq!(Ceremony::initialize(qap(), &mut rng).map(|_| ()), degenerate);
+
+    let (usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    assert_eq!(Ceremony::resume(qap(), usrs, BatchProof::initiate()).map(|_| ()), degenerate);
+}
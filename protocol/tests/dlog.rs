@@ -10,7 +10,6 @@ use ark_ff::fields::Field;          // Needed for pow
 use ark_ff::ToBytes;
 use ark_std::rand::Rng as ArkRng;   // Must be in scope for rscalar
 use ark_std::rand::RngCore;
-use ark_bls12_381;
 
 #[test]
 fn test_dlog_proof() {
@@ -26,7 +25,7 @@ fn test_dlog_proof() {
         let elm2 = smul2!(scalar!(f2), genG2!());
         let commit = (elm1, elm2);
         let witness = scalar!(w);
-        let proof = Dlog::prove(&commit, witness);
+        let proof = Dlog::prove(&commit, &witness).unwrap();
         match expected {
             true => {
                 assert!(Dlog::verify(ctx, &commit, &proof).unwrap());
@@ -40,3 +39,17 @@ fn test_dlog_proof() {
         };
     }
 }
+
+#[test]
+fn test_rndoracle() {
+    let commit = (smul1!(scalar!(100_u64), genG1!()), smul2!(scalar!(100_u64), genG2!()));
+    let other = (smul1!(scalar!(666_u64), genG1!()), smul2!(scalar!(666_u64), genG2!()));
+    let digest = Dlog::rndoracle(&commit).unwrap();
+    assert_eq!(digest, Dlog::rndoracle(&commit).unwrap());
+    assert_ne!(digest, Dlog::rndoracle(&other).unwrap());
+    assert!(digest.is_in_correct_subgroup_assuming_on_curve());
+    assert_eq!(
+        protocol::prover::DLOG_DST,
+        &backend::hash_to_curve::dst_g1("SNARKY-V01-CS01-DLOG")[..]
+    );
+}
@@ -0,0 +1,62 @@
+use circuits::ConstraintSystem;
+use protocol::{SRS, BatchProof, Encoding, SerializationError, TranscriptError};
+use protocol::groth16::Groth16Error;
+use util::{SnarkyError, ShapeError, ProofError};
+use std::error::Error;
+use std::io::Cursor;
+
+// Propagates failures of all crates by `?`
+fn load(bytes: &[u8], m: usize, n: usize, l: usize) -> Result<(ConstraintSystem, SRS), SnarkyError> {
+    let qap = ConstraintSystem::create_default(m, n, l)?;
+    let srs = SRS::read(&mut Cursor::new(bytes), &qap)?;
+    Ok((qap, srs))
+}
+
+#[test]
+fn test_error_propagation() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    let mut batch = BatchProof::initiate();
+    protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+    let mut buffer = Vec::<u8>::new();
+    srs.write(&mut buffer, &qap, Encoding::COMPRESSED).unwrap();
+
+    assert_eq!(load(&buffer, 5, 4, 3).unwrap().1, srs);
+    assert_eq!(
+        load(&buffer, 3, 4, 3).unwrap_err(),
+        SnarkyError::Shape(ShapeError::InputsOutOfRange)
+    );
+    assert_eq!(
+        load(&buffer, 6, 4, 3).unwrap_err(),
+        SnarkyError::Shape(ShapeError::SrsMismatch)
+    );
+    assert_eq!(
+        load(&buffer[..buffer.len() - 1], 5, 4, 3).unwrap_err(),
+        SnarkyError::Io(std::io::ErrorKind::UnexpectedEof)
+    );
+    assert_eq!(
+        load(b"NONE", 5, 4, 3).unwrap_err(),
+        SnarkyError::Serialization(SerializationError::InvalidMagic)
+    );
+}
+
+#[test]
+fn test_error_conversion_and_display() {
+    let err = SnarkyError::from(TranscriptError::BatchFailure);
+    assert_eq!(err, SnarkyError::Proof(ProofError::BatchFailure));
+    assert_eq!(err.to_string(), "Proof failure: invalid batch of update-proofs");
+    assert!(err.source().is_some());
+
+    let err = SnarkyError::from(TranscriptError::BrokenChain(3));
+    assert_eq!(err.to_string(), "Invalid transcript: entry 3 not linked to its previous one");
+
+    let err = SnarkyError::from(TranscriptError::Serialization(SerializationError::InvalidPoint));
+    assert_eq!(err, SnarkyError::MalformedPoint);
+    assert!(err.source().is_none());
+
+    let err = SnarkyError::from(Groth16Error::ShapeMismatch);
+    assert_eq!(err, SnarkyError::Shape(ShapeError::SrsMismatch));
+    let err = SnarkyError::from(Groth16Error::Unsatisfied);
+    assert_eq!(err, SnarkyError::Groth16(Groth16Error::Unsatisfied));
+}
@@ -1,33 +1,61 @@
 use backend::*;
 use circuits::ConstraintSystem;
-use protocol::{SRS, Trapdoor, BatchProof, Phase, Verification};
+use protocol::{SRS, UniversalSRS, Trapdoor, BatchProof, Verification, VerificationStep};
+use protocol::{SnarkyError, ShapeError, ProofError};
 use protocol;
 use num_traits::identities::Zero;
 use ark_ec::AffineCurve;
 
 macro_rules! run_setup {
-    ($qap: expr, $trapdoor:expr) => {
+    ($n: expr, $trapdoor:expr, $rng: expr) => {
         {
             match $trapdoor {
-                "given"  => SRS::setup(&$qap, Some(Trapdoor::from_u64(1, 2, 3, 4))),
-                "random" => SRS::setup_with_random_trapdoor(&$qap),
-                "unit"   => SRS::setup_with_unit_trapdoor(&$qap),
+                "given"  => {
+                    let trp = Trapdoor::from_u64(1, 2, 3, 4);
+                    (UniversalSRS::create(&trp, $n).unwrap(), trp)
+                },
+                "random" => UniversalSRS::setup_with_random_trapdoor($n, $rng).unwrap(),
+                "unit"   => UniversalSRS::setup_with_unit_trapdoor($n).unwrap(),
                 _        => panic!("Trapdoor generation unspecified")
             }
         }
     }
 }
 
+macro_rules! run_universal_updates {
+    ($usrs: expr, $batch: expr, $nr: expr, $rng: expr) => {
+        let mut count = 0;
+        while count < $nr {
+            protocol::update_universal(&mut $usrs, &mut $batch, $rng).unwrap();
+            count += 1
+        }
+    }
+}
+
 macro_rules! run_updates {
-    ($qap: expr, $srs: expr, $batch: expr, $phase: expr, $nr: expr) => {
+    ($qap: expr, $srs: expr, $batch: expr, $nr: expr, $rng: expr) => {
         let mut count = 0;
         while count < $nr {
-            protocol::update(&$qap, &mut $srs, &mut $batch, $phase);
+            protocol::update(&$qap, &mut $srs, &mut $batch, $rng).unwrap();
             count += 1
         }
     }
 }
 
+macro_rules! run_ceremony {
+    ($qap: expr, $trapdoor: expr, $nr_1: expr, $nr_2: expr, $rng: expr) => {
+        {
+            let (_, n, _) = $qap.shape();
+            let (mut usrs, trp) = run_setup!(n, $trapdoor, $rng);
+            let mut batch = BatchProof::initiate();
+            run_universal_updates!(usrs, batch, $nr_1, $rng);
+            let mut srs = usrs.specialize(&$qap).unwrap();
+            run_updates!($qap, srs, batch, $nr_2, $rng);
+            (srs, trp, batch)
+        }
+    }
+}
+
 macro_rules! corrupt {
     ($batch: expr, $comp: expr, $index: expr) => {
         match $comp {
@@ -51,12 +79,9 @@ macro_rules! run_protocol {
         $nr_1: expr => $cor_1: expr, $nr_2: expr => $cor_2: expr
     ) => {
         {
+            let mut rng = ::util::snarky_rng();
             let qap = ConstraintSystem::create_default($m, $n, $l).unwrap();
-            let (mut srs, trp) = run_setup!(qap, $trapdoor);
-
-            let mut batch = BatchProof::initiate();
-            run_updates!(qap, srs, batch, Phase::ONE, $nr_1);
-            run_updates!(qap, srs, batch, Phase::TWO, $nr_2);
+            let (srs, _, mut batch) = run_ceremony!(qap, $trapdoor, $nr_1, $nr_2, &mut rng);
 
             for (j, corrupt) in [$cor_1, $cor_2].iter().enumerate() {
                 let comp = j + 1;
@@ -69,11 +94,15 @@ macro_rules! run_protocol {
                 }
             }
 
-            let res = protocol::verify(&qap, &srs, &batch);
+            let res = protocol::verify(&qap, &srs, &batch, &mut rng);
             assert_eq!(
                 protocol::verify_naive(&qap, &srs, &batch), 
                 res
             );                                              // cross-check
+            assert_eq!(
+                protocol::verify_batched(&qap, &srs, &batch, &mut rng),
+                res
+            );                                              // cross-check
             res
         }
     }
@@ -115,6 +144,210 @@ fn test_success_with_unit_trapdoor() {
     assert_eq!(bool::from(res), true);
 }
 
+#[test]
+fn test_failure_with_srs_tampered() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut srs, _, batch) = run_ceremony!(qap, "random", 2, 2, &mut rng);
+    assert_eq!(protocol::verify_batched(&qap, &srs, &batch, &mut rng), Verification::SUCCESS);
+
+    srs.s.3[0] = add1!(srs.s.3[0], genG1!());       // violates step 14 only
+    assert_eq!(protocol::verify(&qap, &srs, &batch, &mut rng), Verification::FAILURE);
+    assert_eq!(protocol::verify_batched(&qap, &srs, &batch, &mut rng), Verification::FAILURE);
+
+    let report = protocol::verify_detailed(&qap, &srs, &batch, &mut rng);
+    assert_eq!(Verification::from(&report), Verification::FAILURE);
+    let failures = report.failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].step, VerificationStep::TComponent);
+    assert_eq!(failures[0].index, None);
+}
+
+#[test]
+fn test_detailed_verification_locates_contribution() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, _, mut batch) = run_ceremony!(qap, "random", 3, 3, &mut rng);
+
+    let report = protocol::verify_detailed(&qap, &srs, &batch, &mut rng);
+    assert!(report.passed());
+    assert_eq!(report.steps.len(), VerificationStep::ALL.len());
+    assert_eq!(report.first_failure(), None);
+
+    corrupt!(batch, 2, 1);
+    let report = protocol::verify_detailed(&qap, &srs, &batch, &mut rng);
+    assert_eq!(Verification::from(&report), protocol::verify(&qap, &srs, &batch, &mut rng));
+    let failure = report.first_failure().unwrap();
+    assert_eq!(failure.step, VerificationStep::UpdateProofsTwo);
+    assert_eq!(failure.index, Some(1));
+
+    corrupt!(batch, 1, 2);
+    let report = protocol::verify_detailed(&qap, &srs, &batch, &mut rng);
+    let failure = report.first_failure().unwrap();
+    assert_eq!(failure.step, VerificationStep::UpdateProofsOne);
+    assert_eq!(failure.index, Some(2));
+}
+
+#[test]
+fn test_seeded_ceremony_is_reproducible() {
+    let ceremony = |seed| {
+        let mut rng = ::util::seeded_rng(seed);
+        let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+        let (srs, trp, batch) = run_ceremony!(qap, "random", 2, 2, &mut rng);
+        assert!(bool::from(protocol::verify(&qap, &srs, &batch, &mut rng)));
+        (srs, trp, batch)
+    };
+    let (first, second) = (ceremony(7), ceremony(7));
+    assert_eq!((&first.0, &first.2), (&second.0, &second.2));
+    assert!(ct_eq!(first.1, second.1));
+    assert_ne!(ceremony(7).0, ceremony(8).0);
+}
+
+#[test]
+fn test_incremental_verification_of_updates() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    let mut batch = BatchProof::initiate();
+    for _ in 0..2 {
+        let prev = usrs.clone();
+        let proof = protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+        assert_eq!(protocol::verify_update_universal(&prev, &usrs, &proof, &mut rng), Ok(()));
+    }
+    let mut srs = usrs.specialize(&qap).unwrap();
+    for _ in 0..2 {
+        let prev = srs.clone();
+        let proof = protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+        assert_eq!(protocol::verify_update(&qap, &prev, &srs, &proof, &mut rng), Ok(()));
+    }
+    assert!(bool::from(protocol::verify(&qap, &srs, &batch, &mut rng)));
+}
+
+#[test]
+fn test_incremental_verification_rejects_bad_update() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut srs, _, mut batch) = run_ceremony!(qap, "random", 1, 0, &mut rng);
+    let prev = srs.clone();
+    let proof = protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+    let failure = |err| Err(SnarkyError::Proof(err));
+
+    // Update-proof not linked to the previous SRS
+    let (other, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    assert_eq!(
+        protocol::verify_update(&qap, &other, &srs, &proof, &mut rng),
+        failure(ProofError::RhoFailure)
+    );
+
+    // Updated SRS tampered beyond the elements committed by the update-proof
+    for index in 0..2 {
+        let mut next = srs.clone();
+        match index {
+            0 => next.s.2[1] = add1!(next.s.2[1], genG1!()),
+            _ => next.s.3[0] = add1!(next.s.3[0], genG1!()),
+        }
+        assert_eq!(
+            protocol::verify_update(&qap, &prev, &next, &proof, &mut rng),
+            failure(ProofError::UpdateFailure)
+        );
+    }
+    let mut next = srs.clone();
+    next.s.1 = genG2!();
+    assert_eq!(
+        protocol::verify_update(&qap, &prev, &next, &proof, &mut rng),
+        failure(ProofError::UpdateFailure)
+    );
+
+    // Phase 2 update altering the u-component
+    let mut next = srs.clone();
+    next.u = other.u.clone();
+    assert_eq!(
+        protocol::verify_update(&qap, &prev, &next, &proof, &mut rng),
+        failure(ProofError::UpdateFailure)
+    );
+
+    // Updated SRS of other shape
+    let bigger = ConstraintSystem::create_default(6, 4, 3).unwrap();
+    assert_eq!(
+        protocol::verify_update(&bigger, &prev, &srs, &proof, &mut rng),
+        Err(SnarkyError::Shape(ShapeError::SrsMismatch))
+    );
+}
+
+#[test]
+fn test_incremental_verification_rejects_bad_universal_update() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    let mut batch = BatchProof::initiate();
+    let prev = usrs.clone();
+    let proof = protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+    let failure = |err| Err(SnarkyError::Proof(err));
+
+    // Update-proof of the wrong phase, in either direction
+    let mut srs = usrs.specialize(&qap).unwrap();
+    let before = srs.clone();
+    let proof_2 = protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+    assert_eq!(
+        protocol::verify_update_universal(&prev, &usrs, &proof_2, &mut rng),
+        failure(ProofError::PhaseMismatch)
+    );
+    assert_eq!(
+        protocol::verify_update(&qap, &before, &srs, &proof, &mut rng),
+        failure(ProofError::PhaseMismatch)
+    );
+
+    // Update-proof not linked to the previous universal SRS
+    let (other, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    assert_eq!(
+        protocol::verify_update_universal(&other, &usrs, &proof, &mut rng),
+        failure(ProofError::RhoFailure)
+    );
+
+    // Updated universal SRS tampered beyond the elements committed by the
+    // update-proof
+    for index in 0..2 {
+        let mut next = usrs.clone();
+        match index {
+            0 => next.u.0[2].0 = add1!(next.u.0[2].0, genG1!()),
+            _ => next.u.1[3].1 = add1!(next.u.1[3].1, genG1!()),
+        }
+        assert_eq!(
+            protocol::verify_update_universal(&prev, &next, &proof, &mut rng),
+            failure(ProofError::UpdateFailure)
+        );
+    }
+
+    // Updated universal SRS of other degree or truncated
+    let (bigger, _) = UniversalSRS::setup_with_random_trapdoor(5, &mut rng).unwrap();
+    assert_eq!(
+        protocol::verify_update_universal(&prev, &bigger, &proof, &mut rng),
+        Err(SnarkyError::Shape(ShapeError::SrsMismatch))
+    );
+    let mut truncated = usrs.clone();
+    truncated.u.0.truncate(1);
+    truncated.u.1.truncate(1);
+    assert_eq!(
+        protocol::verify_update_universal(&prev, &truncated, &proof, &mut rng),
+        Err(SnarkyError::Shape(ShapeError::SrsMismatch))
+    );
+}
+
+#[test]
+fn test_success_with_deferred_specialization() {
+    let mut rng = ::util::snarky_rng();
+    let (usrs, _) = UniversalSRS::setup_with_random_trapdoor(6, &mut rng).unwrap();
+    for &(m, n, l) in [(5, 4, 3), (7, 6, 2)].iter() {
+        let qap = ConstraintSystem::create_default(m, n, l).unwrap();
+        let mut usrs = usrs.clone();
+        let mut batch = BatchProof::initiate();
+        run_universal_updates!(usrs, batch, 2, &mut rng);
+        let mut srs = usrs.specialize(&qap).unwrap();
+        run_updates!(qap, srs, batch, 2, &mut rng);
+        assert!(bool::from(protocol::verify(&qap, &srs, &batch, &mut rng)));
+    }
+}
+
 // #[test]
 // fn test_failure_edge_1() {
 //     let res = run_protocol!(5, 4, 3, "unit", 
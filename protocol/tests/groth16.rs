@@ -0,0 +1,254 @@
+use backend::*;
+use circuits::{ConstraintSystem, R1CS, Row};
+use protocol::groth16::{self, Groth16Error, Proof, VerifyingKey, PreparedVerifyingKey};
+use protocol::{BatchProof, Encoding, SnarkyError, SRS, UniversalSRS, Trapdoor};
+use util::SnarkyRng;
+
+use ark_ec::{AffineCurve, PairingEngine};
+use num_traits::identities::Zero;
+
+// x^3 + x + 5 = out, with variables [1, out, x, x^2, x^3, x^3 + x]
+fn cubic() -> ConstraintSystem {
+    let one = scalar!(1u64);
+    let a: Vec<Row> = vec![
+        vec![(2, one)],
+        vec![(3, one)],
+        vec![(4, one), (2, one)],
+        vec![(5, one), (0, scalar!(5u64))],
+    ];
+    let b: Vec<Row> = vec![
+        vec![(2, one)],
+        vec![(2, one)],
+        vec![(0, one)],
+        vec![(0, one)],
+    ];
+    let c: Vec<Row> = vec![
+        vec![(3, one)],
+        vec![(4, one)],
+        vec![(5, one)],
+        vec![(1, one)],
+    ];
+    R1CS::create(a, b, c, 5, 1).unwrap().to_qap().unwrap()
+}
+
+// Runs the provided numbers of phase 1 and phase 2 updates from a random
+// trapdoor, returning the final SRS along with the batch of update-proofs
+fn run_ceremony<R: SnarkyRng>(qap: &ConstraintSystem, nr_1: usize, nr_2: usize, rng: &mut R)
+    -> (SRS, BatchProof)
+{
+    let (_, n, _) = qap.shape();
+    let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(n, rng).unwrap();
+    let mut batch = BatchProof::initiate();
+    for _ in 0..nr_1 {
+        protocol::update_universal(&mut usrs, &mut batch, rng).unwrap();
+    }
+    let mut srs = usrs.specialize(qap).unwrap();
+    for _ in 0..nr_2 {
+        protocol::update(qap, &mut srs, &mut batch, rng).unwrap();
+    }
+    (srs, batch)
+}
+
+fn assignment(x: u64) -> Vec<Scalar> {
+    [1, x * x * x + x + 5, x, x * x, x * x * x, x * x * x + x]
+        .iter()
+        .map(|&v| scalar!(v))
+        .collect()
+}
+
+// e(A, B) = e(alpha, beta) e(sum_{i <= l} z_i [b u_i + a v_i + w_i]_1, H) e(C, delta)
+fn check_proof(qap: &ConstraintSystem, srs: &SRS, inputs: &[Scalar], proof: &Proof) -> bool {
+    let (_, n, l) = qap.shape();
+    let (u, v, w, _) = qap.collections();
+    let mut acc = zeroG1!();
+    for i in 0..l + 1 {
+        for j in 0..n {
+            acc = add1!(
+                acc,
+                smul1!(inputs[i] * u[i].coeff(j), srs.u.1[j].1),
+                smul1!(inputs[i] * v[i].coeff(j), srs.u.1[j].0),
+                smul1!(inputs[i] * w[i].coeff(j), srs.u.0[j].0)
+            );
+        }
+    }
+    pair!(proof.a, proof.b) ==
+        pair!(srs.u.1[0].0, srs.u.1[0].3) *
+        pair!(acc, genG2!()) *
+        pair!(proof.c, srs.s.1)
+}
+
+#[test]
+fn test_prove_with_known_trapdoor() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, trp) = SRS::setup(&qap, Some(Trapdoor::from_u64(2, 3, 5, 7)), &mut rng).unwrap();
+    let z = assignment(3);
+    let (r, s) = (scalar!(11u64), scalar!(13u64));
+    let proof = groth16::create_proof(&qap, &srs, &z, &r, &s).unwrap();
+
+    let (a, b, d, x) = trp.extract();
+    let (u, v, _, _) = qap.collections();
+    let eval = |polys: &Vec<polynomials::Univariate<Scalar>>| polys
+        .iter()
+        .zip(z.iter())
+        .fold(zero!(), |acc, (p, &z_i)| acc + z_i * p.evaluate(&x));
+    assert_eq!(proof.a, smul1!(*a + eval(u) + r * *d, genG1!()));
+    assert_eq!(proof.b, smul2!(*b + eval(v) + s * *d, genG2!()));
+    assert!(check_proof(&qap, &srs, &z[..2], &proof));
+}
+
+#[test]
+fn test_prove_after_updates() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, _) = run_ceremony(&qap, 2, 1, &mut rng);
+
+    let z = assignment(4);
+    let proof = groth16::prove(&qap, &srs, &z, &mut rng).unwrap();
+    assert!(check_proof(&qap, &srs, &z[..2], &proof));
+
+    let other = assignment(5);
+    assert!(!check_proof(&qap, &srs, &other[..2], &proof));
+}
+
+#[test]
+fn test_proofs_are_randomized() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    let z = assignment(3);
+    let proof_1 = groth16::prove(&qap, &srs, &z, &mut rng).unwrap();
+    let proof_2 = groth16::prove(&qap, &srs, &z, &mut rng).unwrap();
+    assert_ne!(proof_1, proof_2);
+    assert!(check_proof(&qap, &srs, &z[..2], &proof_1));
+    assert!(check_proof(&qap, &srs, &z[..2], &proof_2));
+}
+
+#[test]
+fn test_prove_failures() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+
+    let mut z = assignment(3);
+    z[1] = scalar!(36u64);
+    assert_eq!(groth16::prove(&qap, &srs, &z, &mut rng), Err(Groth16Error::Unsatisfied));
+
+    let z = assignment(3);
+    assert_eq!(groth16::prove(&qap, &srs, &z[..5], &mut rng), Err(Groth16Error::InvalidAssignment));
+
+    let mut z = assignment(3);
+    z[0] = scalar!(2u64);
+    assert_eq!(groth16::prove(&qap, &srs, &z, &mut rng), Err(Groth16Error::InvalidAssignment));
+
+    let other = ConstraintSystem::create_default(5, 4, 1).unwrap();
+    let (srs, _) = SRS::setup_with_random_trapdoor(&other, &mut rng);
+    assert_eq!(groth16::prove(&qap, &srs, &assignment(3), &mut rng), Err(Groth16Error::ShapeMismatch));
+}
+
+#[test]
+fn test_ceremony_over_circuit() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, batch) = run_ceremony(&qap, 1, 1, &mut rng);
+    assert!(bool::from(protocol::verify(&qap, &srs, &batch, &mut rng)));
+    assert!(bool::from(protocol::verify_naive(&qap, &srs, &batch)));
+}
+
+#[test]
+fn test_verifying_key() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, trp) = SRS::setup(&qap, Some(Trapdoor::from_u64(2, 3, 5, 7)), &mut rng).unwrap();
+    let vk = VerifyingKey::from_srs(&qap, &srs).unwrap();
+    let (a, b, d, x) = trp.extract();
+    let (u, v, w, _) = qap.collections();
+    assert_eq!(vk.alpha_1, smul1!(*a, genG1!()));
+    assert_eq!(vk.beta_2, smul2!(*b, genG2!()));
+    assert_eq!(vk.delta_2, smul2!(*d, genG2!()));
+    assert_eq!(vk.nr_inputs(), Some(1));
+    for i in 0..2 {
+        let expected = *b * u[i].evaluate(&x) + *a * v[i].evaluate(&x) + w[i].evaluate(&x);
+        assert_eq!(vk.query[i], smul1!(expected, genG1!()));
+    }
+
+    let other = ConstraintSystem::create_default(5, 4, 1).unwrap();
+    assert_eq!(VerifyingKey::from_srs(&other, &srs), Err(Groth16Error::ShapeMismatch));
+
+    let mut empty = vk;
+    empty.query.clear();
+    assert_eq!(empty.nr_inputs(), None);
+    let proof = groth16::prove(&qap, &srs, &assignment(3), &mut rng).unwrap();
+    assert_eq!(empty.validate(&qap), Err(Groth16Error::InvalidVerifyingKey));
+    assert_eq!(
+        groth16::verify_proof(&empty.prepare(), &[], &proof),
+        Err(Groth16Error::InvalidVerifyingKey)
+    );
+}
+
+#[test]
+fn test_verifying_key_serialization() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, _) = run_ceremony(&qap, 1, 1, &mut rng);
+    let vk = VerifyingKey::from_srs(&qap, &srs).unwrap();
+    for encoding in [Encoding::COMPRESSED, Encoding::UNCOMPRESSED].iter() {
+        let mut buffer = Vec::new();
+        vk.write(&mut buffer, *encoding).unwrap();
+        assert_eq!(VerifyingKey::read(&mut &buffer[..], &qap).unwrap(), vk);
+    }
+
+    // Query length other than l + 1
+    let other = ConstraintSystem::create_default(5, 4, 2).unwrap();
+    let mut buffer = Vec::new();
+    vk.write(&mut buffer, Encoding::COMPRESSED).unwrap();
+    assert_eq!(
+        VerifyingKey::read(&mut &buffer[..], &other).unwrap_err(),
+        SnarkyError::Groth16(Groth16Error::InvalidVerifyingKey)
+    );
+    let mut empty = vk;
+    empty.query.clear();
+    let mut buffer = Vec::new();
+    empty.write(&mut buffer, Encoding::COMPRESSED).unwrap();
+    assert_eq!(
+        VerifyingKey::read(&mut &buffer[..], &qap).unwrap_err(),
+        SnarkyError::Groth16(Groth16Error::InvalidVerifyingKey)
+    );
+}
+
+#[test]
+fn test_verify_proof() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs, _) = run_ceremony(&qap, 1, 1, &mut rng);
+    let pvk = VerifyingKey::from_srs(&qap, &srs).unwrap().prepare();
+
+    let z = assignment(3);
+    let proof = groth16::prove(&qap, &srs, &z, &mut rng).unwrap();
+    assert_eq!(groth16::verify_proof(&pvk, &z[1..2], &proof), Ok(true));
+    assert_eq!(groth16::verify_proof(&pvk, &[scalar!(36u64)], &proof), Ok(false));
+    assert_eq!(
+        groth16::verify_proof(&pvk, &z[1..3], &proof),
+        Err(Groth16Error::InvalidPublicInputs)
+    );
+
+    let mut forged = proof.clone();
+    forged.c = add1!(forged.c, genG1!());
+    assert_eq!(groth16::verify_proof(&pvk, &z[1..2], &forged), Ok(false));
+
+    let mut swapped = proof;
+    std::mem::swap(&mut swapped.a, &mut swapped.c);
+    assert_eq!(groth16::verify_proof(&pvk, &z[1..2], &swapped), Ok(false));
+}
+
+#[test]
+fn test_verify_against_other_srs() {
+    let mut rng = ::util::snarky_rng();
+    let qap = cubic();
+    let (srs_1, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    let (srs_2, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    let pvk = PreparedVerifyingKey::from(VerifyingKey::from_srs(&qap, &srs_2).unwrap());
+    let z = assignment(3);
+    let proof = groth16::prove(&qap, &srs_1, &z, &mut rng).unwrap();
+    assert_eq!(groth16::verify_proof(&pvk, &z[1..2], &proof), Ok(false));
+}
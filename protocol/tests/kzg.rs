@@ -0,0 +1,136 @@
+// The Ethereum KZG ceremony is over BLS12-381
+backend::cfg_bls12_381! {
+    use backend::*;
+    use backend::zcash::{g1_to_compressed, g2_to_compressed};
+    use circuits::ConstraintSystem;
+    use protocol::import::ImportError;
+    use protocol::import::kzg::{self, KzgTranscript};
+    use protocol::{BatchProof, Ceremony, CeremonyError, SnarkyError};
+
+    use ark_ec::AffineCurve;
+    use ark_ff::fields::Field;
+    use std::io::Cursor;
+
+    fn to_hex(bytes: &[u8]) -> String {
+        let hex = bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        format!("\"0x{}\"", hex)
+    }
+
+    fn sub_transcript(nr_1: usize, nr_2: usize, tau: u64) -> String {
+        let x = scalar!(tau);
+        let g1 = (0..nr_1)
+            .map(|i| to_hex(&g1_to_compressed(&smul1!(pow!(x, i), genG1!()))))
+            .collect::<Vec<_>>();
+        let g2 = (0..nr_2)
+            .map(|i| to_hex(&g2_to_compressed(&smul2!(pow!(x, i), genG2!()))))
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"numG1Powers\": {}, \"numG2Powers\": {}, \
+            \"powersOfTau\": {{\"G1Powers\": [{}], \"G2Powers\": [{}]}}, \
+            \"witness\": {{}}}}",
+            nr_1, nr_2, g1.join(", "), g2.join(", ")
+        )
+    }
+
+    fn create_transcript(sizes: &[(usize, usize)], tau: u64) -> Vec<u8> {
+        let subs = sizes
+            .iter()
+            .map(|&(nr_1, nr_2)| sub_transcript(nr_1, nr_2, tau))
+            .collect::<Vec<_>>();
+        format!(
+            "{{\"transcripts\": [{}], \"participantIds\": []}}",
+            subs.join(", ")
+        ).into_bytes()
+    }
+
+    #[test]
+    fn test_kzg_max_degree() {
+        let buffer = create_transcript(&[(8, 5), (16, 5)], 7);
+        let transcript = KzgTranscript::read(&mut Cursor::new(&buffer)).unwrap();
+        assert_eq!(transcript.nr_powers(), vec![(8, 5), (16, 5)]);
+        assert_eq!(transcript.max_degree(), 3);
+    }
+
+    #[test]
+    fn test_kzg_import() {
+        let mut rng = ::util::snarky_rng();
+        let buffer = create_transcript(&[(8, 5), (16, 5)], 7);
+        let qap = ConstraintSystem::create_default(5, 3, 3).unwrap();
+        let (mut usrs, mut batch) = kzg::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap();
+        assert_eq!(batch, BatchProof::initiate());
+        assert_eq!(usrs.u.0[4].1, smul2!(pow!(scalar!(7u64), 4), genG2!()));
+        let not_updated = SnarkyError::Import(ImportError::NotUpdated);
+        assert!(usrs.pending_update());
+        assert_eq!(usrs.specialize(&qap).unwrap_err(), not_updated);
+
+        // Nor can a ceremony resumed from the raw import be specialized or finalized
+        let other = ConstraintSystem::create_default(5, 3, 3).unwrap();
+        let mut ceremony = Ceremony::resume(other, usrs.clone(), BatchProof::initiate()).unwrap();
+        assert_eq!(ceremony.specialize().unwrap_err(), not_updated);
+        assert_eq!(
+            ceremony.finalize(&mut rng).map(|_| ()).unwrap_err(),
+            SnarkyError::Ceremony(CeremonyError::NotSpecialized)
+        );
+        assert!(!bool::from(ceremony.verify(&mut rng)));
+        ceremony.contribute(&mut rng).unwrap();
+        assert_eq!(ceremony.specialize(), Ok(()));
+
+        protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+        let mut srs = usrs.specialize(&qap).unwrap();
+        protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+        assert!(bool::from(protocol::verify(&qap, &srs, &batch, &mut rng)));
+    }
+
+    #[test]
+    fn test_kzg_insufficient_powers() {
+        let mut rng = ::util::snarky_rng();
+        let buffer = create_transcript(&[(8, 5), (16, 5)], 7);
+        let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+        assert_eq!(
+            kzg::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap_err(),
+            ImportError::InsufficientPowers { available: 5, required: 7 }
+        );
+    }
+
+    #[test]
+    fn test_kzg_malformed_input() {
+        let mut rng = ::util::snarky_rng();
+        let qap = ConstraintSystem::create_default(5, 3, 3).unwrap();
+        let buffer = b"{\"transcripts\": 1}".to_vec();
+        assert_eq!(
+            kzg::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap_err(),
+            ImportError::InvalidFormat
+        );
+
+        let buffer = String::from_utf8(create_transcript(&[(8, 5)], 7)).unwrap();
+        let tampered = buffer.replacen("\"numG1Powers\": 8", "\"numG1Powers\": 9", 1);
+        assert_eq!(
+            kzg::import(&mut Cursor::new(tampered.as_bytes()), &qap, &mut rng).unwrap_err(),
+            ImportError::InvalidFormat
+        );
+
+        let point = to_hex(&g1_to_compressed(&smul1!(scalar!(7u64), genG1!())));
+        let mut invalid = [0xffu8; 48];                 // x not reduced
+        invalid[0] = 0x9f;
+        let tampered = buffer.replacen(&point, &to_hex(&invalid), 1);
+        assert_eq!(
+            kzg::import(&mut Cursor::new(tampered.as_bytes()), &qap, &mut rng).unwrap_err(),
+            ImportError::InvalidPoint
+        );
+    }
+
+    #[test]
+    fn test_kzg_inconsistent_powers() {
+        let mut rng = ::util::snarky_rng();
+        let qap = ConstraintSystem::create_default(5, 3, 3).unwrap();
+        let buffer = String::from_utf8(create_transcript(&[(8, 5)], 7)).unwrap();
+        let x = scalar!(7u64);
+        let point = to_hex(&g2_to_compressed(&smul2!(pow!(x, 3), genG2!())));
+        let other = to_hex(&g2_to_compressed(&smul2!(pow!(x, 4), genG2!())));
+        let tampered = buffer.replacen(&point, &other, 1);
+        assert_eq!(
+            kzg::import(&mut Cursor::new(tampered.as_bytes()), &qap, &mut rng).unwrap_err(),
+            ImportError::InvalidPowers
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use backend::*;
+use circuits::ConstraintSystem;
+use protocol::import::ImportError;
+use protocol::import::ptau::{self, PtauHeader};
+use protocol::{BatchProof, SnarkyError};
+
+use backend::curve::Fq;
+use ark_ec::AffineCurve;
+use ark_ff::{fields::Field, BigInteger, FpParameters, PrimeField};
+use std::io::Cursor;
+
+// Byte size of base field elements
+const FQ: usize = if backend::BN254 { 32 } else { 48 };
+
+fn write_fq(buffer: &mut Vec<u8>, elem: &Fq) {
+    for limb in (elem.0).0.iter() {             // Montgomery form
+        buffer.extend_from_slice(&limb.to_le_bytes());
+    }
+}
+
+fn write_section(buffer: &mut Vec<u8>, kind: u32, data: &[u8]) {
+    buffer.extend_from_slice(&kind.to_le_bytes());
+    buffer.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    buffer.extend_from_slice(data);
+}
+
+// Minimal .ptau file with sections 1-3 for the given power and tau
+fn create_ptau(power: u32, tau: u64) -> Vec<u8> {
+    let x = scalar!(tau);
+    let size = 1usize << power;
+
+    let mut header = Vec::new();
+    header.extend_from_slice(&(FQ as u32).to_le_bytes());
+    header.extend_from_slice(&<Fq as PrimeField>::Params::MODULUS.to_bytes_le());
+    header.extend_from_slice(&power.to_le_bytes());
+    header.extend_from_slice(&power.to_le_bytes());
+
+    let mut tau_g1 = Vec::new();
+    for i in 0..2 * size - 1 {
+        let elem = smul1!(pow!(x, i), genG1!());
+        write_fq(&mut tau_g1, &elem.x);
+        write_fq(&mut tau_g1, &elem.y);
+    }
+    let mut tau_g2 = Vec::new();
+    for i in 0..size {
+        let elem = smul2!(pow!(x, i), genG2!());
+        for c in [elem.x.c0, elem.x.c1, elem.y.c0, elem.y.c1].iter() {
+            write_fq(&mut tau_g2, c);
+        }
+    }
+
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(b"ptau");
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+    buffer.extend_from_slice(&3u32.to_le_bytes());
+    write_section(&mut buffer, 1, &header);
+    write_section(&mut buffer, 3, &tau_g2);     // Order of sections is irrelevant
+    write_section(&mut buffer, 2, &tau_g1);
+    buffer
+}
+
+#[test]
+fn test_ptau_header() {
+    let buffer = create_ptau(3, 7);
+    let header = ptau::read_header(&mut Cursor::new(&buffer)).unwrap();
+    assert_eq!(header, PtauHeader { power: 3, ceremony_power: 3 });
+    assert_eq!(header.nr_powers(), (15, 8));
+    assert_eq!(header.max_degree(), 4);
+}
+
+#[test]
+fn test_ptau_import() {
+    let mut rng = ::util::snarky_rng();
+    let buffer = create_ptau(3, 7);
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut usrs, mut batch) = ptau::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap();
+    assert_eq!(batch, BatchProof::initiate());
+    assert_eq!(usrs.u.0[1].0, smul1!(scalar!(7u64), genG1!()));
+    assert_eq!(usrs.u.0[6].1, smul2!(pow!(scalar!(7u64), 6), genG2!()));
+    assert!(usrs.pending_update());
+    assert_eq!(usrs.specialize(&qap).unwrap_err(), SnarkyError::Import(ImportError::NotUpdated));
+
+    protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+    let mut srs = usrs.specialize(&qap).unwrap();
+    protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+    assert!(bool::from(protocol::verify(&qap, &srs, &batch, &mut rng)));
+}
+
+#[test]
+fn test_ptau_insufficient_powers() {
+    let mut rng = ::util::snarky_rng();
+    let buffer = create_ptau(2, 7);
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap_err(),
+        ImportError::InsufficientPowers { available: 4, required: 7 }
+    );
+}
+
+#[test]
+fn test_ptau_malformed_input() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let buffer = create_ptau(3, 7);
+
+    let mut corrupted = buffer.clone();
+    corrupted[0] = b'x';
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&corrupted), &qap, &mut rng).unwrap_err(),
+        ImportError::InvalidFormat
+    );
+
+    let mut corrupted = buffer.clone();
+    corrupted[4 + 4 + 4 + 4..4 + 4 + 4 + 4 + 8]
+        .copy_from_slice(&(1u64 << 63).to_le_bytes());  // header size above i64::MAX
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&corrupted), &qap, &mut rng).unwrap_err(),
+        ImportError::InvalidFormat
+    );
+
+    let mut corrupted = buffer.clone();
+    corrupted[4 + 4 + 4 + 4 + 8 + 4] ^= 1;      // first byte of prime
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&corrupted), &qap, &mut rng).unwrap_err(),
+        ImportError::FieldMismatch
+    );
+
+    let mut corrupted = buffer.clone();
+    let len = corrupted.len();
+    corrupted[len - 1] ^= 1;                    // last tauG1 point (unused)
+    assert!(ptau::import(&mut Cursor::new(&corrupted), &qap, &mut rng).is_ok());
+    let offset = 4 + 4 + 4 + (4 + 8 + 4 + FQ + 4 + 4) + (4 + 8 + 8 * 4 * FQ) + (4 + 8);
+    corrupted[offset + 2 * FQ] ^= 1;                // x-coordinate of [x]_1
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&corrupted), &qap, &mut rng).unwrap_err(),
+        ImportError::InvalidPoint
+    );
+}
+
+#[test]
+fn test_ptau_invalid_powers() {
+    let mut rng = ::util::snarky_rng();
+    let mut buffer = create_ptau(3, 7);
+    let offset = 4 + 4 + 4 + (4 + 8 + 4 + FQ + 4 + 4) + (4 + 8 + 8 * 4 * FQ) + (4 + 8);
+    let elem = smul1!(scalar!(2u64), genG1!());
+    let mut bytes = Vec::new();
+    write_fq(&mut bytes, &elem.x);
+    write_fq(&mut bytes, &elem.y);
+    buffer[offset..offset + 2 * FQ].copy_from_slice(&bytes);
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap_err(),
+        ImportError::InvalidPowers
+    );
+}
+
+#[test]
+fn test_ptau_inconsistent_powers() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let offset = 4 + 4 + 4 + (4 + 8 + 4 + FQ + 4 + 4) + (4 + 8 + 8 * 4 * FQ) + (4 + 8);
+
+    // [x^2]_1 replaced by [x^3]_1
+    let mut buffer = create_ptau(3, 7);
+    let elem = smul1!(pow!(scalar!(7u64), 3), genG1!());
+    let mut bytes = Vec::new();
+    write_fq(&mut bytes, &elem.x);
+    write_fq(&mut bytes, &elem.y);
+    buffer[offset + 4 * FQ..offset + 6 * FQ].copy_from_slice(&bytes);
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap_err(),
+        ImportError::InvalidPowers
+    );
+
+    // Powers in G2 of other tau than those in G1
+    let mut buffer = create_ptau(3, 7);
+    let other = create_ptau(3, 8);
+    let range = offset - (4 + 8) - 8 * 4 * FQ..offset - (4 + 8);
+    buffer[range.clone()].copy_from_slice(&other[range]);
+    assert_eq!(
+        ptau::import(&mut Cursor::new(&buffer), &qap, &mut rng).unwrap_err(),
+        ImportError::InvalidPowers
+    );
+}
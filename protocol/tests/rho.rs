@@ -1,4 +1,139 @@
-use protocol::prover::{RhoProof, ProofError};
-use util::map;
+use protocol::prover::{RhoProof, UpdateProof, Witness};
+use protocol::{SRS, Encoding, SerializationError};
+use circuits::ConstraintSystem;
+use backend::*;
+use ark_ec::AffineCurve;
+use std::io::Cursor;
 
-// TODO: Write tests
+// Byte size of base field elements
+const FQ: usize = if backend::BN254 { 32 } else { 48 };
+
+#[test]
+fn test_rho_serialization_roundtrip() {
+    let (G, H) = (genG1!(), genG2!());
+    let rho = RhoProof::create((&G, &H), &G, &scalar!(7u64)).unwrap();
+    for &(encoding, size) in [
+        (Encoding::COMPRESSED, 3 * FQ + 2 * FQ),
+        (Encoding::UNCOMPRESSED, 3 * 2 * FQ + 4 * FQ),
+    ].iter() {
+        let mut buffer = Vec::<u8>::new();
+        rho.write(&mut buffer, encoding).unwrap();
+        assert_eq!(buffer.len(), size);
+        let loaded = RhoProof::read(&mut Cursor::new(&buffer), encoding).unwrap();
+        assert_eq!(loaded, rho);
+        assert!(loaded.verify((&G, &H), None).unwrap());
+    }
+}
+
+#[test]
+fn test_update_proof_serialization_roundtrip() {
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, _) = SRS::setup_with_unit_trapdoor(&qap);
+    let witnesses = [
+        Witness::ONE(scalar!(2u64), scalar!(3u64), scalar!(5u64)),
+        Witness::TWO(scalar!(7u64)),
+    ];
+    for witness in witnesses.iter() {
+        let proof = UpdateProof::create(&srs, witness).unwrap();
+        let mut buffer = Vec::<u8>::new();
+        proof.write(&mut buffer, Encoding::COMPRESSED).unwrap();
+        let loaded = UpdateProof::read(&mut Cursor::new(&buffer), Encoding::COMPRESSED);
+        assert_eq!(loaded.unwrap(), proof);
+    }
+}
+
+#[test]
+fn test_witness_redaction_and_zeroization() {
+    use num_traits::identities::Zero;
+    use std::mem::ManuallyDrop;
+
+    let witness = Witness::ONE(scalar!(2u64), scalar!(3u64), scalar!(5u64));
+    assert_eq!(format!("{:?}", witness), "Witness::ONE(<redacted>)");
+
+    // The memory of the dropped witness remains readable through ManuallyDrop
+    let mut witness = ManuallyDrop::new(witness);
+    unsafe { ManuallyDrop::drop(&mut witness) };
+    assert!(ct_eq!(*witness, Witness::ONE(zero!(), zero!(), zero!())));
+    assert!(ct_ne!(Witness::TWO(zero!()), Witness::ONE(zero!(), zero!(), zero!())));
+    assert_eq!(format!("{:?}", Witness::TWO(scalar!(7u64))), "Witness::TWO(<redacted>)");
+}
+
+#[test]
+fn test_update_consumes_witness() {
+    use protocol::Trapdoor;
+    use protocol::SnarkyError;
+    use zeroize::ZeroizeOnDrop;
+
+    // Witnesses are passed by value to the update, so that they are zeroized
+    // upon its return
+    fn zeroized_on_drop<T: ZeroizeOnDrop>() {}
+    zeroized_on_drop::<Witness>();
+    zeroized_on_drop::<Trapdoor>();
+    let update: fn(&mut SRS, &ConstraintSystem, Witness) -> Result<(), SnarkyError> = SRS::update;
+
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut srs, _) = SRS::setup_with_unit_trapdoor(&qap);
+    update(&mut srs, &qap, Witness::TWO(scalar!(7u64))).unwrap();
+    assert_eq!(srs.s.0, smul1!(scalar!(7u64), genG1!()));
+}
+
+#[test]
+fn test_update_proof_invalid_phase() {
+    let buffer = [3u8; 1];
+    assert_eq!(
+        UpdateProof::read(&mut Cursor::new(&buffer), Encoding::COMPRESSED).unwrap_err(),
+        SerializationError::InvalidPhase
+    );
+}
+
+backend::cfg_bls12_381! {
+    // On-curve point outside the prime order subgroup; BN254 G1 has cofactor 1,
+    // so that no such point exists there
+    fn outside_g1() -> G1Elem {
+        (1u64..)
+            .filter_map(|x| G1Elem::get_point_from_x(backend::curve::Fq::from(x), false))
+            .find(|elem| !elem.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_rho_rejects_points_outside_subgroup() {
+        let (g, h) = (genG1!(), genG2!());
+        let rho = RhoProof::create((&g, &h), &g, &scalar!(7u64)).unwrap();
+        assert!(rho.verify((&g, &h), None).unwrap());
+        let torsion = outside_g1();
+        for k in 0..3 {
+            let mut forged = rho.clone();
+            match k {
+                0 => forged.aux = torsion,
+                1 => forged.com.0 = torsion,
+                _ => forged.prf = torsion,
+            }
+            assert_eq!(
+                forged.verify((&g, &h), Some(&rho)).unwrap_err(),
+                protocol::prover::ProofError::RhoFailure
+            );
+        }
+    }
+
+    #[test]
+    fn test_batch_rejects_points_outside_subgroup() {
+        use protocol::{BatchProof, Phase, Verification};
+
+        let mut rng = ::util::snarky_rng();
+        let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+        let (mut srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+        let mut batch = BatchProof::initiate();
+        for _ in 0..2 {
+            protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+        }
+        let s = (0..2).map(|_| scalar!(1u64)).collect::<Vec<Scalar>>();
+        assert!(batch.equations(&srs, &s, Phase::TWO).1);
+
+        // The proof of the first rho-proof enters no batched equation
+        batch.batch_2[0].prf = outside_g1();
+        assert!(!batch.equations(&srs, &s, Phase::TWO).1);
+        assert_eq!(protocol::verify(&qap, &srs, &batch, &mut rng), Verification::FAILURE);
+        assert_eq!(protocol::verify_naive(&qap, &srs, &batch), Verification::FAILURE);
+    }
+}
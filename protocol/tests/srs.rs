@@ -1,4 +1,351 @@
-use protocol::srs::SRS;
-use util::map;
+use protocol::srs::{SRS, SRSHeader, UniversalSRS};
+use protocol::{BatchProof, Encoding, SerializationError};
+use circuits::ConstraintSystem;
+use util::{SnarkyError, ShapeError, ProofError};
+use std::io::Cursor;
 
-// TODO: Write tests
+// Byte size of base field elements
+const FQ: usize = if backend::BN254 { 32 } else { 48 };
+
+macro_rules! serialize {
+    ($srs:expr, $qap:expr, $encoding:expr) => {
+        {
+            let mut buffer = Vec::<u8>::new();
+            $srs.write(&mut buffer, &$qap, $encoding).unwrap();
+            buffer
+        }
+    }
+}
+
+#[test]
+fn test_srs_serialization_roundtrip() {
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (mut srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    let mut batch = BatchProof::initiate();
+    protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+    for encoding in [Encoding::COMPRESSED, Encoding::UNCOMPRESSED].iter() {
+        let buffer = serialize!(srs, qap, *encoding);
+        let loaded = SRS::read(&mut Cursor::new(&buffer), &qap).unwrap();
+        assert_eq!(loaded, srs);
+    }
+}
+
+#[test]
+fn test_srs_serialization_sizes() {
+    let (m, n, l) = (5, 4, 3);
+    let qap = ConstraintSystem::create_default(m, n, l).unwrap();
+    let (srs, _) = SRS::setup_with_unit_trapdoor(&qap);
+    let header = 4 + 1 + 1 + 7 * 8;
+    let parametrization = [
+        (Encoding::COMPRESSED, FQ, 2 * FQ),
+        (Encoding::UNCOMPRESSED, 2 * FQ, 4 * FQ),
+    ];
+    for &(encoding, size1, size2) in parametrization.iter() {
+        let nr_1 = (2 * n - 1) + 2 * n + 1 + (m - l) + (n - 1);
+        let nr_2 = (2 * n - 1) + 2 * n + 1;
+        let buffer = serialize!(srs, qap, encoding);
+        assert_eq!(buffer.len(), header + nr_1 * size1 + nr_2 * size2);
+    }
+}
+
+#[test]
+fn test_srs_header() {
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, _) = SRS::setup_with_unit_trapdoor(&qap);
+    let buffer = serialize!(srs, qap, Encoding::COMPRESSED);
+    let header = SRSHeader::read(&mut Cursor::new(&buffer)).unwrap();
+    assert_eq!(header, SRSHeader {
+        version: protocol::serialize::VERSION,
+        encoding: Encoding::COMPRESSED,
+        shape: (5, 4, 3),
+    });
+    assert_eq!(header.validate(&qap), Ok(()));
+    let other = ConstraintSystem::create_default(6, 4, 3).unwrap();
+    assert_eq!(header.validate(&other), Err(SerializationError::ShapeMismatch));
+}
+
+#[test]
+fn test_srs_shape_mismatch() {
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, _) = SRS::setup_with_unit_trapdoor(&qap);
+    let buffer = serialize!(srs, qap, Encoding::COMPRESSED);
+    let other = ConstraintSystem::create_default(5, 5, 3).unwrap();
+    assert_eq!(
+        SRS::read(&mut Cursor::new(&buffer), &other).unwrap_err(),
+        SerializationError::ShapeMismatch
+    );
+    assert_eq!(
+        srs.write(&mut Vec::new(), &other, Encoding::COMPRESSED).unwrap_err(),
+        SerializationError::ShapeMismatch
+    );
+}
+
+#[test]
+fn test_srs_malformed_input() {
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (srs, _) = SRS::setup_with_unit_trapdoor(&qap);
+    let buffer = serialize!(srs, qap, Encoding::UNCOMPRESSED);
+
+    let mut corrupted = buffer.clone();
+    corrupted[0] = b'X';
+    assert_eq!(
+        SRS::read(&mut Cursor::new(&corrupted), &qap).unwrap_err(),
+        SerializationError::InvalidMagic
+    );
+
+    let mut corrupted = buffer.clone();
+    corrupted[4] = 0xff;
+    assert_eq!(
+        SRS::read(&mut Cursor::new(&corrupted), &qap).unwrap_err(),
+        SerializationError::InvalidVersion(0xff)
+    );
+
+    let mut corrupted = buffer.clone();
+    corrupted[4 + 1 + 1 + 7 * 8] ^= 1;     // first coordinate of first point
+    assert_eq!(
+        SRS::read(&mut Cursor::new(&corrupted), &qap).unwrap_err(),
+        SerializationError::InvalidPoint
+    );
+
+    let truncated = &buffer[..buffer.len() - 1];
+    assert_eq!(
+        SRS::read(&mut Cursor::new(truncated), &qap).unwrap_err(),
+        SerializationError::IoFailure(std::io::ErrorKind::UnexpectedEof)
+    );
+}
+
+// BN254 G1 has cofactor 1
+backend::cfg_bls12_381! {
+    #[test]
+    fn test_srs_rejects_points_outside_subgroup() {
+        let mut rng = ::util::snarky_rng();
+        let torsion = (1u64..)
+            .filter_map(|x| backend::G1Elem::get_point_from_x(backend::curve::Fq::from(x), false))
+            .find(|elem| !elem.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+        let (srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+        assert!(srs.check_u(&qap).is_ok());
+        assert!(srs.check_s(&qap).is_ok());
+
+        for k in 0..4 {
+            let (mut forged, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+            match k {
+                0 => forged.u.0[1].0 = torsion,
+                1 => forged.u.1[2].0 = torsion,
+                2 => forged.u.1[3].1 = torsion,
+                _ => forged.u.1[0].1 = torsion,
+            }
+            assert_eq!(forged.check_u(&qap).unwrap_err(), SnarkyError::MalformedPoint);
+        }
+        let (mut forged, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+        forged.s.2[0] = torsion;
+        assert_eq!(forged.check_s(&qap).unwrap_err(), SnarkyError::MalformedPoint);
+    }
+}
+
+#[test]
+fn test_srs_creation_values() {
+    use protocol::Trapdoor;
+    use backend::*;
+    use ark_ec::AffineCurve;
+    use ark_ff::Field;
+
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let (m, n, l) = qap.shape();
+    let (u, v, w, t) = qap.collections();
+    let trp = Trapdoor::from_u64(2, 3, 5, 7);
+    let (a, b, d, x) = trp.extract();
+    let (a, b, d, x) = (*a, *b, *d, *x);
+    let srs = SRS::create(&trp, &qap).unwrap();
+    let (g, h) = (genG1!(), genG2!());
+
+    for i in 0..2 * n - 1 {
+        assert_eq!(srs.u.0[i], (smul1!(pow!(x, i), g), smul2!(pow!(x, i), h)));
+    }
+    for i in 0..n {
+        assert_eq!(srs.u.1[i], (
+            smul1!(a * pow!(x, i), g),
+            smul1!(b * pow!(x, i), g),
+            smul2!(a * pow!(x, i), h),
+            smul2!(b * pow!(x, i), h),
+        ));
+    }
+    let dinv = inv!(d).unwrap();
+    assert_eq!((srs.s.0, srs.s.1), (smul1!(d, g), smul2!(d, h)));
+    for i in 0..m - l {
+        let k = l + 1 + i;
+        let val = b * u[k].evaluate(&x) + a * v[k].evaluate(&x) + w[k].evaluate(&x);
+        assert_eq!(srs.s.2[i], smul1!(val * dinv, g));
+    }
+    for i in 0..n - 1 {
+        assert_eq!(srs.s.3[i], smul1!(pow!(x, i) * t.evaluate(&x) * dinv, g));
+    }
+}
+
+#[test]
+fn test_trapdoor_redaction_and_zeroization() {
+    use protocol::Trapdoor;
+    use backend::*;
+    use num_traits::identities::Zero;
+    use std::mem::ManuallyDrop;
+
+    let trp = Trapdoor::from_u64(2, 3, 5, 7);
+    assert_eq!(format!("{:?}", trp), "Trapdoor(<redacted>)");
+    assert!(!format!("{:?}", trp).contains(&format!("{:?}", scalar!(7u64))));
+
+    // The memory of dropped values remains readable through ManuallyDrop
+    let mut copies = ManuallyDrop::new(trp.extract());
+    assert_eq!(*copies.3, scalar!(7u64));
+    unsafe { ManuallyDrop::drop(&mut copies) };
+    assert_eq!(
+        (*copies.0, *copies.1, *copies.2, *copies.3),
+        (zero!(), zero!(), zero!(), zero!())
+    );
+
+    let mut trp = ManuallyDrop::new(trp);
+    unsafe { ManuallyDrop::drop(&mut trp) };
+    let (a, b, d, x) = trp.extract();
+    assert_eq!((*a, *b, *d, *x), (zero!(), zero!(), zero!(), zero!()));
+}
+
+#[test]
+fn test_srs_errors_upon_bad_input() {
+    use protocol::Trapdoor;
+    use protocol::prover::Witness;
+    use backend::*;
+
+    let mut rng = ::util::snarky_rng();
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    assert_eq!(
+        SRS::create(&Trapdoor::from_u64(2, 3, 0, 7), &qap).unwrap_err(),
+        SnarkyError::ZeroScalar
+    );
+    assert!(SRS::setup(&qap, Some(Trapdoor::from_u64(2, 3, 0, 7)), &mut rng).is_err());
+
+    let other = ConstraintSystem::create_default(6, 5, 3).unwrap();
+    let (mut srs, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    let mismatch = SnarkyError::Shape(ShapeError::SrsMismatch);
+    assert_eq!(srs.check_shape(&other).unwrap_err(), mismatch);
+    assert_eq!(srs.check_u(&other).unwrap_err(), mismatch);
+    assert_eq!(srs.check_s(&other).unwrap_err(), mismatch);
+
+    let mut batch = BatchProof::initiate();
+    let before = srs.clone();
+    assert_eq!(
+        protocol::update(&other, &mut srs, &mut batch, &mut rng).unwrap_err(),
+        mismatch
+    );
+    assert_eq!((srs.clone(), batch), (before.clone(), BatchProof::initiate()));
+
+    // Phase 1 runs only on the universal SRS
+    let witness = Witness::ONE(scalar!(2u64), scalar!(3u64), scalar!(7u64));
+    assert_eq!(
+        srs.update(&qap, witness).unwrap_err(),
+        SnarkyError::Proof(ProofError::PhaseMismatch)
+    );
+    assert_eq!(srs, before);
+    assert!(!bool::from(protocol::verify(&other, &srs, &BatchProof::initiate(), &mut rng)));
+    assert!(!bool::from(protocol::verify_naive(&other, &srs, &BatchProof::initiate())));
+}
+
+#[test]
+fn test_universal_srs_specialization() {
+    use protocol::Trapdoor;
+
+    let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+    let trp = Trapdoor::from_u64(2, 3, 1, 7);
+    let expected = SRS::create(&trp, &qap).unwrap();
+    for n in [4, 6].iter() {
+        let usrs = UniversalSRS::create(&trp, *n).unwrap();
+        assert_eq!(usrs.degree(), *n);
+        assert_eq!(usrs.specialize(&qap).unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_universal_srs_errors_upon_bad_input() {
+    use protocol::prover::Witness;
+    use backend::*;
+
+    let mut rng = ::util::snarky_rng();
+    assert_eq!(
+        UniversalSRS::setup_with_random_trapdoor(0, &mut rng).unwrap_err(),
+        SnarkyError::Shape(ShapeError::EmptyCircuit)
+    );
+    assert_eq!(
+        UniversalSRS::setup_with_random_trapdoor(1, &mut rng).unwrap_err(),
+        SnarkyError::Shape(ShapeError::TooFewConstraints)
+    );
+    let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, &mut rng).unwrap();
+    let bigger = ConstraintSystem::create_default(6, 5, 3).unwrap();
+    assert_eq!(
+        usrs.specialize(&bigger).unwrap_err(),
+        SnarkyError::Shape(ShapeError::SrsMismatch)
+    );
+
+    let before = usrs.clone();
+    assert_eq!(
+        usrs.update(Witness::TWO(scalar!(5u64))).unwrap_err(),
+        SnarkyError::Proof(ProofError::PhaseMismatch)
+    );
+    assert_eq!(usrs, before);
+
+    usrs.u.0.pop();
+    assert_eq!(
+        usrs.update(Witness::ONE(scalar!(2u64), scalar!(3u64), scalar!(5u64))).unwrap_err(),
+        SnarkyError::Shape(ShapeError::SrsMismatch)
+    );
+}
+
+// Regression: specialization once ranged over the first m - l polynomials
+// instead of those of the private variables l + 1..m, and dropped the leading
+// coefficient of t; the verifier matched, so that such SRS verified
+#[test]
+fn test_specialization_over_private_variables() {
+    use circuits::{R1CS, Row};
+    use protocol::Trapdoor;
+    use backend::*;
+    use ark_ec::AffineCurve;
+    use ark_ff::Field;
+    use num_traits::identities::Zero;
+
+    let one = scalar!(1u64);
+    let a: Vec<Row> = vec![
+        vec![(2, one)],
+        vec![(3, one)],
+        vec![(4, one), (2, one)],
+        vec![(5, one), (0, scalar!(5u64))],
+    ];
+    let b: Vec<Row> = vec![vec![(2, one)], vec![(2, one)], vec![(0, one)], vec![(0, one)]];
+    let c: Vec<Row> = vec![vec![(3, one)], vec![(4, one)], vec![(5, one)], vec![(1, one)]];
+    let qap = R1CS::create(a, b, c, 5, 1).unwrap().to_qap().unwrap();
+    let (m, n, l) = qap.shape();
+    let (u, v, w, t) = qap.collections();
+    let (a, b, x) = (scalar!(2u64), scalar!(3u64), scalar!(7u64));
+    let g = genG1!();
+
+    let mut rng = ::util::snarky_rng();
+    let trp = Trapdoor::from_u64(2, 3, 1, 7);
+    let srs = SRS::create(&trp, &qap).unwrap();
+    assert!(bool::from(protocol::verify(&qap, &srs, &BatchProof::initiate(), &mut rng)));
+
+    let mut forged = SRS::create(&trp, &qap).unwrap();
+    for i in 0..m - l {
+        let val = b * u[i].evaluate(&x) + a * v[i].evaluate(&x) + w[i].evaluate(&x);
+        assert_ne!(srs.s.2[i], smul1!(val, g));
+        forged.s.2[i] = smul1!(val, g);
+    }
+    assert!(!bool::from(protocol::verify(&qap, &forged, &BatchProof::initiate(), &mut rng)));
+    assert!(!bool::from(protocol::verify_naive(&qap, &forged, &BatchProof::initiate())));
+
+    let mut forged = SRS::create(&trp, &qap).unwrap();
+    let truncated = (0..n).fold(zero!(), |acc, j| acc + t.coeff(j) * pow!(x, j));
+    for i in 0..n - 1 {
+        forged.s.3[i] = smul1!(pow!(x, i) * truncated, g);
+    }
+    assert_ne!(forged.s.3, srs.s.3);
+    assert!(!bool::from(protocol::verify(&qap, &forged, &BatchProof::initiate(), &mut rng)));
+    assert!(!bool::from(protocol::verify_naive(&qap, &forged, &BatchProof::initiate())));
+}
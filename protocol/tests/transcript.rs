@@ -0,0 +1,156 @@
+use circuits::ConstraintSystem;
+use protocol::transcript::{Entry, srs_digest, universal_digest};
+use protocol::{SRS, UniversalSRS, BatchProof, Phase, Encoding, Transcript, TranscriptError};
+use std::fs::File;
+use std::io::{Cursor, Read};
+use std::path::PathBuf;
+
+macro_rules! tmp_path {
+    ($name:expr) => {
+        {
+            let path = std::env::temp_dir().join(
+                format!("snarky-{}-{}.trn", $name, std::process::id())
+            );
+            let _ = std::fs::remove_file(&path);
+            path
+        }
+    }
+}
+
+macro_rules! run_ceremony {
+    ($path:expr, $encoding:expr, $nr_1:expr, $nr_2:expr, $rng:expr) => {
+        {
+            let qap = ConstraintSystem::create_default(5, 4, 3).unwrap();
+            let (mut usrs, _) = UniversalSRS::setup_with_random_trapdoor(4, $rng).unwrap();
+            let mut batch = BatchProof::initiate();
+            let mut transcript = Transcript::create(&$path, $encoding).unwrap();
+            let (nr_1, nr_2): (usize, usize) = ($nr_1, $nr_2);
+            for _ in 0..nr_1 {
+                let proof = protocol::update_universal(&mut usrs, &mut batch, $rng).unwrap();
+                transcript.append_universal(&usrs, &proof).unwrap();
+            }
+            let mut srs = usrs.specialize(&qap).unwrap();
+            for _ in 0..nr_2 {
+                let proof = protocol::update(&qap, &mut srs, &mut batch, $rng).unwrap();
+                transcript.append(&qap, &srs, &proof).unwrap();
+            }
+            (qap, usrs, srs, batch)
+        }
+    }
+}
+
+fn read_file(path: &PathBuf) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    File::open(path).unwrap().read_to_end(&mut buffer).unwrap();
+    buffer
+}
+
+#[test]
+fn test_transcript_replay() {
+    let mut rng = ::util::snarky_rng();
+    let path = tmp_path!("replay");
+    let (qap, _, srs, batch) = run_ceremony!(path, Encoding::COMPRESSED, 3, 2, &mut rng);
+    let replayed = Transcript::verify(&mut File::open(&path).unwrap(), &qap, &srs, &mut rng).unwrap();
+    assert_eq!(replayed, batch);
+    assert!(bool::from(protocol::verify(&qap, &srs, &replayed, &mut rng)));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_transcript_reopen_and_append() {
+    let mut rng = ::util::snarky_rng();
+    let path = tmp_path!("reopen");
+    let (qap, _, mut srs, mut batch) = run_ceremony!(path, Encoding::UNCOMPRESSED, 2, 0, &mut rng);
+    let mut transcript = Transcript::open(&path).unwrap();
+    assert_eq!(transcript.len(), 2);
+    let proof = protocol::update(&qap, &mut srs, &mut batch, &mut rng).unwrap();
+    transcript.append(&qap, &srs, &proof).unwrap();
+    assert_eq!(transcript.len(), 3);
+    let replayed = Transcript::verify(&mut File::open(&path).unwrap(), &qap, &srs, &mut rng).unwrap();
+    assert_eq!(replayed, batch);
+    assert!(Transcript::create(&path, Encoding::COMPRESSED).is_err());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_transcript_entries() {
+    let mut rng = ::util::snarky_rng();
+    let path = tmp_path!("entries");
+    let (qap, usrs, srs, _) = run_ceremony!(path, Encoding::COMPRESSED, 1, 1, &mut rng);
+    let buffer = read_file(&path);
+    let (encoding, entries) = Transcript::read_entries(&mut Cursor::new(&buffer)).unwrap();
+    assert_eq!(encoding, Encoding::COMPRESSED);
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].index, 0);
+    assert_eq!(entries[0].phase(), Phase::ONE);
+    assert_eq!(entries[1].index, 1);
+    assert_eq!(entries[1].phase(), Phase::TWO);
+    assert_eq!(entries[0].digest, universal_digest(&usrs).unwrap());
+    assert_eq!(entries[1].prev, entries[0].hash(encoding).unwrap());
+    assert_eq!(entries[1].digest, srs_digest(&srs, &qap).unwrap());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_transcript_broken_chain() {
+    let mut rng = ::util::snarky_rng();
+    let path = tmp_path!("broken");
+    let (qap, _, srs, _) = run_ceremony!(path, Encoding::COMPRESSED, 2, 1, &mut rng);
+    let buffer = read_file(&path);
+    let (encoding, entries) = Transcript::read_entries(&mut Cursor::new(&buffer)).unwrap();
+
+    // Drop the middle entry, relabeling the last one
+    let mut tampered = Vec::<u8>::new();
+    tampered.extend_from_slice(&buffer[..6]);
+    entries[0].write(&mut tampered, encoding).unwrap();
+    let entry = Entry { index: 1, ..entries[2].clone() };
+    entry.write(&mut tampered, encoding).unwrap();
+    assert_eq!(
+        Transcript::verify(&mut Cursor::new(&tampered), &qap, &srs, &mut rng).unwrap_err(),
+        TranscriptError::BrokenChain(1)
+    );
+
+    // Flip a byte inside the stored SRS digest of the first entry
+    let mut tampered = Vec::<u8>::new();
+    tampered.extend_from_slice(&buffer[..6]);
+    let mut entry = entries[0].clone();
+    entry.digest[0] ^= 1;
+    let mut bytes = Vec::<u8>::new();
+    entry.write(&mut bytes, encoding).unwrap();
+    let len = bytes.len();
+    bytes[len - 32..].copy_from_slice(&entries[0].hash(encoding).unwrap());
+    tampered.extend_from_slice(&bytes);
+    assert_eq!(
+        Transcript::verify(&mut Cursor::new(&tampered), &qap, &srs, &mut rng).unwrap_err(),
+        TranscriptError::BrokenChain(0)
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_transcript_digest_mismatch() {
+    let mut rng = ::util::snarky_rng();
+    let path = tmp_path!("digest");
+    let (qap, _, _, _) = run_ceremony!(path, Encoding::COMPRESSED, 1, 1, &mut rng);
+    let (other, _) = SRS::setup_with_random_trapdoor(&qap, &mut rng);
+    assert_eq!(
+        Transcript::verify(&mut File::open(&path).unwrap(), &qap, &other, &mut rng).unwrap_err(),
+        TranscriptError::DigestMismatch
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_transcript_invalid_phase_order() {
+    let mut rng = ::util::snarky_rng();
+    let path = tmp_path!("order");
+    let (qap, mut usrs, srs, mut batch) = run_ceremony!(path, Encoding::COMPRESSED, 1, 1, &mut rng);
+    let mut transcript = Transcript::open(&path).unwrap();
+    let proof = protocol::update_universal(&mut usrs, &mut batch, &mut rng).unwrap();
+    transcript.append_universal(&usrs, &proof).unwrap();
+    assert_eq!(
+        Transcript::verify(&mut File::open(&path).unwrap(), &qap, &srs, &mut rng).unwrap_err(),
+        TranscriptError::InvalidPhase(2)
+    );
+    std::fs::remove_file(&path).unwrap();
+}
@@ -1,35 +1,406 @@
-use std;
+// Errors of the workspace. Each subsystem has its own kind of error, all of
+// which convert into SnarkyError, so that failures propagate across crates
+// by `?`. Upon conversion, I/O, malformed point and shape failures are
+// lifted to the respective top-level variants regardless of their origin.
+
 use std::fmt;
 
-#[derive(Debug, Clone)]
-pub struct SnarkyError {
-    pub message: String,
-    pub cause: String,
-    pub file: String,
-    pub line: u32,
-    pub code: u32,
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SnarkyError {
+    Shape(ShapeError),
+    MalformedPoint,             // Not on the curve or outside the subgroup
+    ZeroScalar,                 // Scalar required to be invertible is zero
+    Proof(ProofError),
+    Io(std::io::ErrorKind),
+    Hash(HashError),
+    Circuit(CircuitError),
+    Serialization(SerializationError),
+    Transcript(TranscriptError),
+    Import(ImportError),
+    Groth16(Groth16Error),
+    Ceremony(CeremonyError),
+}
+
+
+// Indicates dimensions inconsistent with each other
+#[derive(Clone, Debug, PartialEq)]
+pub enum ShapeError {
+    UnequalLengths,             // u, v, w (resp. A, B, C) of unequal lengths
+    InputsOutOfRange,           // l is not < m
+    DegreeMismatch,             // Polynomial of degree other than n - 1
+    IndexOutOfRange,            // Variable index exceeding m
+    DomainTooLarge,             // No evaluation domain of sufficient size
+    EmptyCircuit,               // No variables or constraints
+    TooFewConstraints,          // Degree n is less than 2
+    SrsMismatch,                // SRS does not match the QAP
+}
+
+// Indicates proof-verification failure
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProofError {
+    DlogFailure,
+    RhoFailure,
+    BatchFailure,
+    UpdateFailure,              // Updated SRS inconsistent with the update-proof
+    PhaseMismatch,              // Update-proof of other phase than expected
+}
+
+// Indicates invalid hash-to-curve parameters (RFC 9380, Section 5.3.1)
+#[derive(Clone, Debug, PartialEq)]
+pub enum HashError {
+    EmptyDst,
+    DstTooLong,
+    LengthTooLarge,
+}
+
+// Indicates failure to load or synthesize a circuit
+#[derive(Clone, Debug, PartialEq)]
+pub enum CircuitError {
+    InvalidFormat(&'static str),    // Malformed circom file
+    FieldMismatch,                  // Prime differs from the backend scalar field
+    Synthesis(String),
+}
+
+// Indicates (de)serialization failure
+#[derive(Clone, Debug, PartialEq)]
+pub enum SerializationError {
+    IoFailure(std::io::ErrorKind),
+    InvalidMagic,
+    InvalidVersion(u8),
+    InvalidEncoding(u8),
+    InvalidPoint,
+    InvalidPhase,
+    ShapeMismatch,
+}
+
+// Indicates transcript failure
+#[derive(Clone, Debug, PartialEq)]
+pub enum TranscriptError {
+    Serialization(SerializationError),
+    InvalidIndex(u64),          // Entry index out of sequence
+    InvalidPhase(u64),          // Phase 1 entry after phase 2 entries
+    BrokenChain(u64),           // Entry not linked to its previous one
+    DigestMismatch,             // Last entry does not commit to the given SRS
+    BatchFailure,
+}
+
+// Indicates import failure
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportError {
+    Serialization(SerializationError),
+    InvalidFormat,
+    MissingSection(u32),
+    FieldMismatch,          // Base field differs from that of the backend
+    InvalidPoint,
+    InvalidPowers,          // Powers do not start from the group generators
+    InsufficientPowers {    // QAP needs more powers than available
+        available: usize,
+        required: usize,
+    },
+    NotUpdated,             // Imported parameters used before a phase 1 update
+}
+
+// Indicates proof-generation failure
+#[derive(Clone, Debug, PartialEq)]
+pub enum Groth16Error {
+    InvalidAssignment,          // Wrong length or first entry not unit
+    Unsatisfied,                // Assignment does not satisfy the QAP
+    ShapeMismatch,              // SRS does not match the QAP
+    InvalidPublicInputs,        // Number of public inputs other than l
+    InvalidVerifyingKey,        // Query empty or of length other than l + 1
+}
+
+// Indicates operation illegal at the current stage of the ceremony
+#[derive(Clone, Debug, PartialEq)]
+pub enum CeremonyError {
+    AlreadySpecialized,         // Phase 1 operation after specialization
+    NotSpecialized,             // Finalization before specialization
+    Finalized,                  // Operation after finalization
+    TooFewConstraints,          // QAP of degree less than 2
 }
 
-impl SnarkyError {
-    pub fn create(message: &str, cause: &str, file: &str, line: u32, code: u32) -> Self {
-        Self { 
-            message: message.to_string(), 
-            cause: cause.to_string(),
-            file: file.to_string(), 
-            line,
-            code,
+
+impl fmt::Display for SnarkyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnarkyError::Shape(err)         => write!(f, "Shape mismatch: {}", err),
+            SnarkyError::MalformedPoint     => write!(f, "Malformed point"),
+            SnarkyError::ZeroScalar         => write!(f, "Zero scalar where invertible required"),
+            SnarkyError::Proof(err)         => write!(f, "Proof failure: {}", err),
+            SnarkyError::Io(kind)           => write!(f, "I/O failure: {:?}", kind),
+            SnarkyError::Hash(err)          => write!(f, "Could not hash to curve: {}", err),
+            SnarkyError::Circuit(err)       => write!(f, "Could not load circuit: {}", err),
+            SnarkyError::Serialization(err) => write!(f, "Could not (de)serialize: {}", err),
+            SnarkyError::Transcript(err)    => write!(f, "Invalid transcript: {}", err),
+            SnarkyError::Import(err)        => write!(f, "Could not import: {}", err),
+            SnarkyError::Groth16(err)       => write!(f, "Could not prove: {}", err),
+            SnarkyError::Ceremony(err)      => write!(f, "Illegal ceremony transition: {}", err),
         }
     }
 }
 
-impl fmt::Display for SnarkyError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "SnarkyError[{}] ({}:{}): {}: {}", 
-            self.code,
-            self.file, 
-            self.line, 
-            self.message,
-            self.cause,
-        )
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cause = match self {
+            ShapeError::UnequalLengths      => "unequal lengths for u, v, w (or A, B, C)",
+            ShapeError::InputsOutOfRange    => "l is not < m",
+            ShapeError::DegreeMismatch      => "detected degree unequal to n - 1",
+            ShapeError::IndexOutOfRange     => "detected variable index exceeding m",
+            ShapeError::DomainTooLarge      => "no evaluation domain of sufficient size",
+            ShapeError::EmptyCircuit        => "no variables or constraints",
+            ShapeError::TooFewConstraints   => "degree n is less than 2",
+            ShapeError::SrsMismatch         => "SRS does not match the QAP",
+        };
+        write!(f, "{}", cause)
+    }
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cause = match self {
+            ProofError::DlogFailure     => "invalid proof of knowledge",
+            ProofError::RhoFailure      => "invalid update-proof",
+            ProofError::BatchFailure    => "invalid batch of update-proofs",
+            ProofError::UpdateFailure   => "updated SRS inconsistent with update-proof",
+            ProofError::PhaseMismatch   => "update-proof of unexpected phase",
+        };
+        write!(f, "{}", cause)
+    }
+}
+
+impl fmt::Display for HashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cause = match self {
+            HashError::EmptyDst         => "empty domain separation tag",
+            HashError::DstTooLong       => "domain separation tag too long",
+            HashError::LengthTooLarge   => "requested length too large",
+        };
+        write!(f, "{}", cause)
+    }
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CircuitError::InvalidFormat(cause)  => write!(f, "{}", cause),
+            CircuitError::FieldMismatch         => write!(f, "prime does not match the backend scalar field"),
+            CircuitError::Synthesis(cause)      => write!(f, "synthesis failed: {}", cause),
+        }
+    }
+}
+
+impl fmt::Display for SerializationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerializationError::IoFailure(kind)     => write!(f, "I/O failure: {:?}", kind),
+            SerializationError::InvalidMagic        => write!(f, "invalid magic"),
+            SerializationError::InvalidVersion(v)   => write!(f, "unsupported version {}", v),
+            SerializationError::InvalidEncoding(e)  => write!(f, "unsupported encoding {}", e),
+            SerializationError::InvalidPoint        => write!(f, "invalid point"),
+            SerializationError::InvalidPhase        => write!(f, "invalid phase"),
+            SerializationError::ShapeMismatch       => write!(f, "shape mismatch"),
+        }
+    }
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranscriptError::Serialization(err) => write!(f, "{}", err),
+            TranscriptError::InvalidIndex(i)    => write!(f, "entry {} out of sequence", i),
+            TranscriptError::InvalidPhase(i)    => write!(f, "phase 1 entry {} after phase 2", i),
+            TranscriptError::BrokenChain(i)     => write!(f, "entry {} not linked to its previous one", i),
+            TranscriptError::DigestMismatch     => write!(f, "last entry does not commit to the SRS"),
+            TranscriptError::BatchFailure       => write!(f, "invalid batch of update-proofs"),
+        }
+    }
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Serialization(err)     => write!(f, "{}", err),
+            ImportError::InvalidFormat          => write!(f, "invalid format"),
+            ImportError::MissingSection(kind)   => write!(f, "missing section {}", kind),
+            ImportError::FieldMismatch          => write!(f, "base field differs from the backend"),
+            ImportError::InvalidPoint           => write!(f, "invalid point"),
+            ImportError::InvalidPowers          => write!(f, "powers do not start from the generators"),
+            ImportError::InsufficientPowers { available, required } => {
+                write!(f, "{} powers available, {} required", available, required)
+            },
+            ImportError::NotUpdated             => write!(f, "imported parameters not updated"),
+        }
+    }
+}
+
+impl fmt::Display for Groth16Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cause = match self {
+            Groth16Error::InvalidAssignment     => "invalid assignment",
+            Groth16Error::Unsatisfied           => "assignment does not satisfy the QAP",
+            Groth16Error::ShapeMismatch         => "SRS does not match the QAP",
+            Groth16Error::InvalidPublicInputs   => "number of public inputs other than l",
+            Groth16Error::InvalidVerifyingKey   => "verifying key query of length other than l + 1",
+        };
+        write!(f, "{}", cause)
+    }
+}
+
+impl fmt::Display for CeremonyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cause = match self {
+            CeremonyError::AlreadySpecialized   => "phase 1 is already complete",
+            CeremonyError::NotSpecialized       => "phase 2 has not started",
+            CeremonyError::Finalized            => "ceremony is finalized",
+            CeremonyError::TooFewConstraints    => "QAP degree is less than 2",
+        };
+        write!(f, "{}", cause)
+    }
+}
+
+
+impl std::error::Error for SnarkyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnarkyError::Shape(err)         => Some(err),
+            SnarkyError::Proof(err)         => Some(err),
+            SnarkyError::Hash(err)          => Some(err),
+            SnarkyError::Circuit(err)       => Some(err),
+            SnarkyError::Serialization(err) => Some(err),
+            SnarkyError::Transcript(err)    => Some(err),
+            SnarkyError::Import(err)        => Some(err),
+            SnarkyError::Groth16(err)       => Some(err),
+            SnarkyError::Ceremony(err)      => Some(err),
+            _                               => None,
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+impl std::error::Error for ProofError {}
+impl std::error::Error for HashError {}
+impl std::error::Error for CircuitError {}
+impl std::error::Error for SerializationError {}
+impl std::error::Error for TranscriptError {}
+impl std::error::Error for ImportError {}
+impl std::error::Error for Groth16Error {}
+impl std::error::Error for CeremonyError {}
+
+
+impl From<std::io::Error> for SnarkyError {
+    fn from(err: std::io::Error) -> Self {
+        SnarkyError::Io(err.kind())
+    }
+}
+
+impl From<ShapeError> for SnarkyError {
+    fn from(err: ShapeError) -> Self {
+        SnarkyError::Shape(err)
+    }
+}
+
+impl From<ProofError> for SnarkyError {
+    fn from(err: ProofError) -> Self {
+        SnarkyError::Proof(err)
+    }
+}
+
+impl From<HashError> for SnarkyError {
+    fn from(err: HashError) -> Self {
+        SnarkyError::Hash(err)
+    }
+}
+
+impl From<CircuitError> for SnarkyError {
+    fn from(err: CircuitError) -> Self {
+        SnarkyError::Circuit(err)
+    }
+}
+
+impl From<SerializationError> for SnarkyError {
+    fn from(err: SerializationError) -> Self {
+        match err {
+            SerializationError::IoFailure(kind) => SnarkyError::Io(kind),
+            SerializationError::InvalidPoint    => SnarkyError::MalformedPoint,
+            SerializationError::ShapeMismatch   => SnarkyError::Shape(ShapeError::SrsMismatch),
+            _                                   => SnarkyError::Serialization(err),
+        }
+    }
+}
+
+impl From<TranscriptError> for SnarkyError {
+    fn from(err: TranscriptError) -> Self {
+        match err {
+            TranscriptError::Serialization(err) => SnarkyError::from(err),
+            TranscriptError::BatchFailure       => SnarkyError::Proof(ProofError::BatchFailure),
+            _                                   => SnarkyError::Transcript(err),
+        }
+    }
+}
+
+impl From<ImportError> for SnarkyError {
+    fn from(err: ImportError) -> Self {
+        match err {
+            ImportError::Serialization(err) => SnarkyError::from(err),
+            ImportError::InvalidPoint       => SnarkyError::MalformedPoint,
+            _                               => SnarkyError::Import(err),
+        }
+    }
+}
+
+impl From<Groth16Error> for SnarkyError {
+    fn from(err: Groth16Error) -> Self {
+        match err {
+            Groth16Error::ShapeMismatch => SnarkyError::Shape(ShapeError::SrsMismatch),
+            _                           => SnarkyError::Groth16(err),
+        }
+    }
+}
+
+impl From<CeremonyError> for SnarkyError {
+    fn from(err: CeremonyError) -> Self {
+        SnarkyError::Ceremony(err)
+    }
+}
+
+impl From<std::io::Error> for SerializationError {
+    fn from(err: std::io::Error) -> Self {
+        SerializationError::IoFailure(err.kind())
+    }
+}
+
+impl From<ark_serialize::SerializationError> for SerializationError {
+    fn from(err: ark_serialize::SerializationError) -> Self {
+        match err {
+            ark_serialize::SerializationError::IoError(err) => {
+                SerializationError::IoFailure(err.kind())
+            },
+            _ => SerializationError::InvalidPoint,
+        }
+    }
+}
+
+impl From<SerializationError> for TranscriptError {
+    fn from(err: SerializationError) -> Self {
+        TranscriptError::Serialization(err)
+    }
+}
+
+impl From<std::io::Error> for TranscriptError {
+    fn from(err: std::io::Error) -> Self {
+        TranscriptError::Serialization(SerializationError::from(err))
+    }
+}
+
+impl From<SerializationError> for ImportError {
+    fn from(err: SerializationError) -> Self {
+        ImportError::Serialization(err)
+    }
+}
+
+impl From<std::io::Error> for ImportError {
+    fn from(err: std::io::Error) -> Self {
+        ImportError::Serialization(SerializationError::from(err))
     }
 }
@@ -3,6 +3,9 @@ mod parser;
 mod macros;
 mod error;
 
-pub use rand_helper::snarky_rng;
+pub use rand_helper::{SnarkyRng, snarky_rng, seeded_rng};
 pub use parser::parse_arg;
-pub use error::SnarkyError;
+pub use error::{
+    SnarkyError, ShapeError, ProofError, HashError, CircuitError,
+    SerializationError, TranscriptError, ImportError, Groth16Error, CeremonyError,
+};
@@ -1,7 +1,21 @@
-use ark_std::rand::SeedableRng;
+use ark_std::rand::{CryptoRng, RngCore, SeedableRng};
+use ark_std::rand::rngs::StdRng;
 
-pub fn snarky_rng() -> ::ark_std::rand::prelude::StdRng {
-    let seed: [u8; 32] = ::rand::random();  
-    let mut rng = ::ark_std::rand::rngs::StdRng::from_seed(seed);
-    rng
+// Randomness source expected by the protocol, passed explicitly wherever
+// secret or verifier scalars are drawn
+pub trait SnarkyRng: RngCore + CryptoRng {}
+
+impl<R: RngCore + CryptoRng> SnarkyRng for R {}
+
+// Seeded from OS entropy; meant to be created once per process
+pub fn snarky_rng() -> StdRng {
+    let mut seed = [0u8; 32];
+    ::rand::RngCore::fill_bytes(&mut ::rand::rngs::OsRng, &mut seed);
+    StdRng::from_seed(seed)
+}
+
+// Deterministic, for tests and audits: the same seed reproduces the same
+// ceremony (with a fixed version of the rand crate)
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
 }